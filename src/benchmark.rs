@@ -2,7 +2,11 @@
 use crate::game::{ Game };
 use std::time::{ SystemTime };
 use crate::openings::{ OpeningsDatabase };
-use crate::consts::{ SEARCH_DEPTH };
+use crate::consts::{ SEARCH_DEPTH, DEFAULT_HASH_SIZE };
+use crate::transposition_table::{ TranspositionTable };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchContext, SearchOptions };
+use std::sync::{ Arc };
 use std::io;
 use std::io::Write;
 use std::fs::{ File };
@@ -16,11 +20,12 @@ pub fn run_benchmarks(save_file: Option<String>) {
 
     println!("[ === Starting benchmarks === ]\n");
     let opening_db = OpeningsDatabase::new();
+    let transposition_table = Arc::new(TranspositionTable::new(DEFAULT_HASH_SIZE));
 
-    let fen_codes = vec![
+    let fen_codes = [
         ("start_code", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 0".to_string()),
-        ("mate_in_2_rooks", "7k/7r/5r2/K7/8/8/8/8 b KQkq - 0 1".to_string()),
-        ("promote_for_black", "8/1k6/8/8/8/8/7p/K7 w KQkq - 0 1".to_string()),
+        ("mate_in_2_rooks", "7k/7r/5r2/K7/8/8/8/8 b - - 0 1".to_string()),
+        ("promote_for_black", "8/1k6/8/8/8/8/7p/K7 w - - 0 1".to_string()),
         ("game_against_serochess", "r2qkbnr/2pNpppp/p1Q5/8/3p2b1/2P5/PP1PPP1P/RNB1KB1R w KQkq - 0 9".to_string()),
         ("castle_black", "4k2r/8/8/8/8/8/8/4KR2 b k - 0 1".to_string())
     ];
@@ -28,15 +33,18 @@ pub fn run_benchmarks(save_file: Option<String>) {
     let mut results = Vec::new();
 
     for (idx, fen_code) in fen_codes.iter().enumerate() {
-        let game = Game::from_fen(fen_code.1.to_string());
+        let game = Game::from_fen(fen_code.1.to_string()).unwrap();
         let mut recorded_times = Vec::new();
 
         print!("{: >2}/{: <2} {: <30}", idx + 1, fen_codes.len(), fen_code.0);
         io::stdout().flush().unwrap();
 
         for _ in 0..10 {
+            // every run starts without the results of the previous run
+            transposition_table.clear();
             let start = SystemTime::now();
-            game.get_best_move(SEARCH_DEPTH, &opening_db);
+            let search = SearchContext::new(SearchLimits::depth(SEARCH_DEPTH), SearchOptions::default(), game.on_turn, &transposition_table);
+            game.get_best_move(&search, &opening_db);
             recorded_times.push(start.elapsed().unwrap().as_nanos());
             print!("|");
             io::stdout().flush().unwrap();
@@ -0,0 +1,267 @@
+//! [Bitboards](https://www.chessprogramming.org/Bitboards) are 64 bit sets of squares, where bit ``y * 8 + x`` stands for the square ``[x, y]``.
+//! 
+//! Has the precomputed attack tables for the knight, king and pawns
+//! and the [magic bitboard](https://www.chessprogramming.org/Magic_Bitboards) lookups for the sliding pieces.
+
+use crate::types::{ Bitboard, Cord };
+use std::sync::{ OnceLock };
+
+
+pub const KNIGHT_ATTACKS: [Bitboard; 64] = leaper_attacks(&[[-1, 2], [1, 2], [2, 1], [2, -1], [1, -2], [-1, -2], [-2, -1], [-2, 1]]);
+pub const KING_ATTACKS: [Bitboard; 64] = leaper_attacks(&[[1, 0], [1, 1], [0, 1], [-1, 1], [-1, 0], [-1, -1], [0, -1], [1, -1]]);
+/// Squares attacked by a pawn, indexed by the color of the pawn.
+pub const PAWN_ATTACKS: [[Bitboard; 64]; 2] = [
+    leaper_attacks(&[[1, 1], [-1, 1]]),
+    leaper_attacks(&[[1, -1], [-1, -1]]),
+];
+
+const ROOK_DIRECTIONS: [[i8; 2]; 4] = [[1, 0], [0, 1], [-1, 0], [0, -1]];
+const BISHOP_DIRECTIONS: [[i8; 2]; 4] = [[1, 1], [-1, 1], [-1, -1], [1, -1]];
+
+// Found with a random search for numbers that map every relevant occupancy to a unique index.
+const ROOK_MAGICS: [u64; 64] = [
+    0x1080004008801020, 0x0840092002c03000, 0x1900200010400900, 0x0880100008000480,
+    0x4200100420080200, 0x8100020100080400, 0x0200040110886200, 0x0200008040220411,
+    0x0404800084400220, 0x0000401000402000, 0x0086001081220440, 0x0408800800100280,
+    0x000a001201040820, 0x8848800200840080, 0x4001000100040200, 0x0442000102105084,
+    0x9080010020804100, 0x0040404000201009, 0x0000808010002009, 0x2200090021d00100,
+    0x0008008008040080, 0x0004004002010040, 0x0011040008015042, 0x00000a0001768104,
+    0x0000800080204009, 0x2010004140002001, 0x9800200280100080, 0x1000100080080080,
+    0x0442000a00049020, 0x2100040080020080, 0x0800120400900148, 0x0010040a00128541,
+    0x2800804000800030, 0x1010002000400041, 0x4000200011004100, 0x0610008410800800,
+    0x0400802402800800, 0xc100020080800400, 0x0002000802000401, 0x0182085882000401,
+    0x0220204000808000, 0x2860100040024022, 0x0001002004110040, 0x99101042000a0020,
+    0x0004080004008080, 0x0010040002008080, 0x2012004881020004, 0x8300842444820011,
+    0x0088403882010200, 0x0820400080210100, 0x0110910040a00300, 0x0801100280080480,
+    0x0242009008200600, 0x1002000489500200, 0x0040800200010080, 0x0091800041000080,
+    0x0000209300488001, 0x04c1002414824001, 0x020020000b001041, 0x7000100004200901,
+    0x8002002004100802, 0x30010002084c0007, 0x0888221800813004, 0x4000002840840112,
+];
+
+const BISHOP_MAGICS: [u64; 64] = [
+    0xa010041108003100, 0x006082020a002900, 0x6810010619200000, 0x08281a0520000408,
+    0x0001104001000400, 0x0018901008048400, 0x00040a0210245280, 0x000200210808a402,
+    0x9140048410821200, 0x0800091010820041, 0x20504804832202c0, 0x0100091401081000,
+    0x8021011140000012, 0x0810020804450400, 0x208b0542109008a2, 0x0080084a08040204,
+    0x0040e2a80811244c, 0x2505022008008108, 0x0430220100420040, 0x010a040420220040,
+    0x1105000290400000, 0x0093001200822120, 0x4000a62048043004, 0x280120048a015004,
+    0x006090002a020814, 0x44042000240800d0, 0x01102800040a4400, 0x1004080080220040,
+    0x0001001011004024, 0x0010044000805040, 0x0914041200820100, 0x0004821012821480,
+    0x0024040500c05021, 0x0088611002080200, 0x0116080a00040020, 0x4000020080080080,
+    0x2450450140840040, 0x0000880201484100, 0x0222020404020092, 0x8081110600002e00,
+    0x2842101105000801, 0x1100809008001025, 0x00020202221c0400, 0x0422014022009020,
+    0x0210046102100c00, 0xc004008082029102, 0x00aa461801101200, 0x0404080080201108,
+    0x020542108c205002, 0x0410544804100100, 0x0040910841100000, 0x0400200042021100,
+    0x00004204850400c0, 0x0200100410a42102, 0x1040020801210102, 0x0805040410420000,
+    0x2884804130100200, 0x800c262201242000, 0x1058000194108800, 0x0014221054420204,
+    0x0104000012a02200, 0x0200881003300100, 0x0140400202840100, 0x0402020801010201,
+];
+
+static SLIDING_ATTACKS: OnceLock<SlidingAttacks> = OnceLock::new();
+
+
+#[derive(Clone, Copy, Default)]
+struct Magic {
+    mask: Bitboard,
+    magic: u64,
+    shift: u32,
+    offset: usize,
+}
+
+impl Magic {
+    fn index(&self, occupied: Bitboard) -> usize {
+        //! Index into the attack table for the specified occupancy.
+
+        self.offset + ((occupied & self.mask).wrapping_mul(self.magic) >> self.shift) as usize
+    }
+}
+
+struct SlidingAttacks {
+    rook: [Magic; 64],
+    bishop: [Magic; 64],
+    table: Vec<Bitboard>,
+}
+
+impl SlidingAttacks {
+    fn new() -> SlidingAttacks {
+        //! Fills the attack table for every square and every relevant occupancy.
+
+        let mut table = Vec::new();
+        let rook = init_magics(&ROOK_DIRECTIONS, &ROOK_MAGICS, &mut table);
+        let bishop = init_magics(&BISHOP_DIRECTIONS, &BISHOP_MAGICS, &mut table);
+
+        SlidingAttacks {
+            rook,
+            bishop,
+            table,
+        }
+    }
+}
+
+pub struct Squares(Bitboard);
+
+impl Iterator for Squares {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let square = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(square)
+    }
+}
+
+
+const fn leaper_attacks(offsets: &[[i8; 2]]) -> [Bitboard; 64] {
+    //! Generate the attack table for a piece that jumps with the specified offsets.
+
+    let mut attacks = [0; 64];
+    let mut square = 0;
+    while square < 64 {
+        let mut idx = 0;
+        while idx < offsets.len() {
+            let x = (square % 8) as i8 + offsets[idx][0];
+            let y = (square / 8) as i8 + offsets[idx][1];
+            if 0 <= x && x <= 7 && 0 <= y && y <= 7 {
+                attacks[square] |= 1 << (y * 8 + x);
+            }
+            idx += 1;
+        }
+        square += 1;
+    }
+
+    attacks
+}
+
+fn init_magics(directions: &[[i8; 2]; 4], magics: &[u64; 64], table: &mut Vec<Bitboard>) -> [Magic; 64] {
+    //! Calculate the masks and fill the attack table for one type of sliding piece.
+
+    let mut result = [Magic::default(); 64];
+    for square in 0..64 {
+        // the edges don't matter for the attacks, unless the piece moves along them
+        let mut mask = 0;
+        for direction in directions.iter() {
+            mask |= sliding_attacks(square, 0, &[*direction]) & !edge_square(square, direction);
+        }
+
+        let magic = Magic {
+            mask,
+            magic: magics[square],
+            shift: 64 - mask.count_ones(),
+            offset: table.len(),
+        };
+        table.resize(table.len() + (1 << mask.count_ones()), 0);
+
+        // walk over all subsets of the mask (Carry-Rippler)
+        let mut occupied: Bitboard = 0;
+        loop {
+            let attacks = sliding_attacks(square, occupied, directions);
+            // an occupancy may share a slot with another only if the attacks are the same
+            debug_assert!(
+                table[magic.index(occupied)] == 0 || table[magic.index(occupied)] == attacks,
+                "destructive magic collision on square {}", square,
+            );
+            table[magic.index(occupied)] = attacks;
+            occupied = occupied.wrapping_sub(mask) & mask;
+            if occupied == 0 { break; }
+        }
+
+        result[square] = magic;
+    }
+
+    result
+}
+
+fn edge_square(square: usize, direction: &[i8; 2]) -> Bitboard {
+    //! Returns the last square on the board when walking from the square in the specified direction.
+    //! Or 0 if the square is already on that edge.
+
+    let mut x = (square % 8) as i8;
+    let mut y = (square / 8) as i8;
+    let mut last = 0;
+    while (0..8).contains(&(x + direction[0])) && (0..8).contains(&(y + direction[1])) {
+        x += direction[0];
+        y += direction[1];
+        last = 1 << (y * 8 + x);
+    }
+
+    last
+}
+
+pub fn sliding_attacks(square: usize, occupied: Bitboard, directions: &[[i8; 2]]) -> Bitboard {
+    //! Walk from the square in the specified directions until a piece is encountered or the edge is reached.
+    //! 
+    //! Slow, only used to fill the tables.
+
+    let mut attacks = 0;
+    for direction in directions.iter() {
+        let mut x = (square % 8) as i8;
+        let mut y = (square / 8) as i8;
+        loop {
+            x += direction[0];
+            y += direction[1];
+            if !((0..8).contains(&x) && (0..8).contains(&y)) {
+                break;
+            }
+
+            let bit = 1 << (y * 8 + x);
+            attacks |= bit;
+            if occupied & bit != 0 {
+                break;
+            }
+        }
+    }
+
+    attacks
+}
+
+fn sliding_tables() -> &'static SlidingAttacks {
+    SLIDING_ATTACKS.get_or_init(SlidingAttacks::new)
+}
+
+pub fn rook_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    //! Squares attacked by a rook on the specified square with the specified pieces on the board.
+
+    let tables = sliding_tables();
+    tables.table[tables.rook[square].index(occupied)]
+}
+
+pub fn bishop_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    //! Squares attacked by a bishop on the specified square with the specified pieces on the board.
+
+    let tables = sliding_tables();
+    tables.table[tables.bishop[square].index(occupied)]
+}
+
+pub fn queen_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    //! Squares attacked by a queen on the specified square with the specified pieces on the board.
+
+    rook_attacks(square, occupied) | bishop_attacks(square, occupied)
+}
+
+pub fn squares(bitboard: Bitboard) -> Squares {
+    //! Iterate over the square indexes of the bits that are set.
+
+    Squares(bitboard)
+}
+
+pub fn square_index(cord: Cord) -> usize {
+    //! [x, y] -> y * 8 + x
+
+    cord[1] * 8 + cord[0]
+}
+
+pub fn index_to_cord(square: usize) -> Cord {
+    //! y * 8 + x -> [x, y]
+
+    [square % 8, square / 8]
+}
+
+pub fn cord_bitboard(cord: Cord) -> Bitboard {
+    //! Bitboard with only the specified square set.
+
+    1 << square_index(cord)
+}
@@ -2,6 +2,13 @@
 
 pub const SEARCH_DEPTH: u8 = 5;
 pub const CHECK_MATE_SCORE: i64 = i64::MAX;
+pub const MAX_PLY: usize = 128;
+/// Deepest iteration of the iterative deepening.
+pub const MAX_SEARCH_DEPTH: u8 = 64;
+/// Scores above this (or below minus this) mean there is a forced mate.
+pub const MATE_BOUND: i64 = CHECK_MATE_SCORE - MAX_PLY as i64;
+/// Default size of the transposition table in MB.
+pub const DEFAULT_HASH_SIZE: usize = 16;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum PieceType {
@@ -20,6 +27,17 @@ pub enum Color {
     Black,
 }
 
+impl Color {
+    pub fn other(&self) -> Color {
+        //! Returns the color of the opponent.
+
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum MoveType {
     Standard,
@@ -28,9 +46,58 @@ pub enum MoveType {
     EnPassant,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+    Exact = 1,
+    Lower = 2,
+    Upper = 3,
+}
+
 #[derive(Debug, Clone, PartialEq, Copy)]
 pub enum GamePhase {
     Start = 0,
     Mid = 1,
     End = 2,
 }
+
+/// Whether a game is over, and why.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameStatus {
+    Ongoing,
+    /// The color that gave the mate won.
+    Checkmate(Color),
+    Stalemate,
+    ThreefoldRepetition,
+    FiftyMoveRule,
+    InsufficientMaterial,
+    SeventyFiveMove,
+}
+
+impl GameStatus {
+    #[allow(dead_code)]
+    pub fn is_over(&self) -> bool {
+        *self != GameStatus::Ongoing
+    }
+
+    #[allow(dead_code)]
+    pub fn winner(&self) -> Option<Color> {
+        //! Returns the color that won, None for a draw or an unfinished game.
+
+        match self {
+            GameStatus::Checkmate(color) => Some(*color),
+            _ => None,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn result(&self) -> &'static str {
+        //! Returns the result as written in a PGN: ``1-0``, ``0-1``, ``1/2-1/2`` or ``*`` for an unfinished game.
+
+        match self {
+            GameStatus::Ongoing => "*",
+            GameStatus::Checkmate(Color::White) => "1-0",
+            GameStatus::Checkmate(Color::Black) => "0-1",
+            _ => "1/2-1/2",
+        }
+    }
+}
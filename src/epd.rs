@@ -0,0 +1,188 @@
+//! EPD mode, for running test suites (WAC, STS, ECM etc.) to measure the tactical strength of the engine.
+//!
+//! Every line of an [EPD file](https://www.chessprogramming.org/Extended_Position_Description) is a position followed by operations.
+//! The ``bm`` (best moves), ``am`` (avoid moves) and ``id`` operations are used, the others are ignored.
+
+use crate::game::{ Game };
+use crate::move_struct::{ Move };
+use crate::transposition_table::{ TranspositionTable };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchContext, SearchOptions };
+use crate::openings::{ OpeningsDatabase };
+use crate::consts::{ DEFAULT_HASH_SIZE };
+use std::sync::{ Arc };
+use std::time::{ Instant };
+use std::io;
+use std::io::Write;
+use std::fs;
+
+
+/// A position of a test suite.
+pub struct EpdPosition {
+    pub game: Game,
+    pub id: String,
+    /// The position is solved by playing one of these moves.
+    pub best_moves: Vec<Move>,
+    /// The position is solved by playing none of these moves.
+    pub avoid_moves: Vec<Move>,
+}
+
+impl EpdPosition {
+    pub fn from_epd(line: &str) -> Result<EpdPosition, String> {
+        //! Load a position from a line of an EPD file, for example:
+        //!
+        //! ```
+        //! 2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";
+        //! ```
+
+        // the FEN without the clocks, which are the hmvc and fmvn operations
+        let mut rest = line.trim();
+        let mut fields = Vec::new();
+        for _ in 0..4 {
+            rest = rest.trim_start();
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            fields.push(&rest[..end]);
+            rest = &rest[end..];
+        }
+
+        let operations = split_operations(rest)?;
+        let operands = |opcode: &str| operations.iter().find(|(name, _)| name == opcode).map(|(_, operands)| operands.clone());
+        let clock = |opcode: &str, default: &str| operands(opcode).and_then(|operands| operands.first().cloned()).unwrap_or(String::from(default));
+
+        let fen = format!("{} {} {}", fields.join(" "), clock("hmvc", "0"), clock("fmvn", "1"));
+        let game = Game::from_fen(fen).map_err(|error| format!("Invalid position `{}`: {}", fields.join(" "), error))?;
+
+        // the moves are in SAN, but some suites use long algebraic notation
+        let parse_moves = |opcode: &str| -> Result<Vec<Move>, String> {
+            operands(opcode).unwrap_or_default().iter().map(|notation| {
+                Move::from_san(notation, &game).or_else(|| game.find_legal_move(notation))
+                    .ok_or_else(|| format!("Illegal move `{}` in `{}`", notation, line.trim()))
+            }).collect()
+        };
+        let best_moves = parse_moves("bm")?;
+        let avoid_moves = parse_moves("am")?;
+        if best_moves.is_empty() && avoid_moves.is_empty() {
+            return Err(format!("No bm or am operation in `{}`", line.trim()));
+        }
+        let id = operands("id").map(|operands| operands.join(" ")).unwrap_or_default();
+
+        Ok(EpdPosition { game, id, best_moves, avoid_moves })
+    }
+
+    pub fn is_solved_by(&self, mve: &Move) -> bool {
+        !self.avoid_moves.contains(mve) && (self.best_moves.is_empty() || self.best_moves.contains(mve))
+    }
+}
+
+
+pub fn read_epd_file(file_name: &str) -> Result<Vec<EpdPosition>, String> {
+    //! Load all the positions of an EPD file, skipping empty lines.
+
+    let contents = fs::read_to_string(file_name).map_err(|error| format!("Can't read `{}`: {}", file_name, error))?;
+    contents.lines().filter(|line| !line.trim().is_empty()).map(EpdPosition::from_epd).collect()
+}
+
+pub fn run_epd(file_name: String, depth: u8, movetime: Option<u64>) {
+    //! Search every position of the EPD file to the specified depth, or for the specified time (in milliseconds),
+    //! and print which positions are solved.
+
+    let positions = match read_epd_file(&file_name) {
+        Ok(positions) => positions,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        },
+    };
+
+    println!("[ === Running {} positions of {} === ]\n", positions.len(), file_name);
+    let opening_db = OpeningsDatabase::new();
+    let transposition_table = Arc::new(TranspositionTable::new(DEFAULT_HASH_SIZE));
+    let limits = match movetime {
+        Some(movetime) => SearchLimits { movetime: Some(movetime), ..Default::default() },
+        None => SearchLimits::depth(depth),
+    };
+    let options = SearchOptions { own_book: false, move_overhead: 0, ..Default::default() };
+
+    let start = Instant::now();
+    let mut solved = 0;
+    let mut failed = Vec::new();
+    for (idx, position) in positions.iter().enumerate() {
+        print!("{: >4}/{: <4} {: <20}", idx + 1, positions.len(), position.id);
+        io::stdout().flush().unwrap();
+
+        // every position starts without the results of the previous one
+        transposition_table.clear();
+        let position_start = Instant::now();
+        let search = SearchContext::new(limits.clone(), options.clone(), position.game.on_turn, &transposition_table);
+        let best_move = position.game.get_best_move(&search, &opening_db);
+
+        let expected = position.best_moves.iter().map(|mve| mve.to_san(&position.game))
+            .chain(position.avoid_moves.iter().map(|mve| format!("not {}", mve.to_san(&position.game))))
+            .collect::<Vec<String>>().join(" ");
+        let played = best_move.map_or(String::from("-"), |mve| mve.to_san(&position.game));
+        let is_solved = best_move.is_some_and(|mve| position.is_solved_by(&mve));
+        if is_solved {
+            solved += 1;
+        } else {
+            failed.push(position.id.as_str());
+        }
+        println!(
+            "{: <8} {: <8} (expected {}) {}ms, {} nodes ({} in quiescence), {:.1}% cutoffs on the first move",
+            played, if is_solved { "solved" } else { "failed" }, expected, position_start.elapsed().as_millis(), search.nodes(), search.qnodes(),
+            search.first_move_cutoff_rate() * 100.0,
+        );
+    }
+
+    println!("\nRESULTS:");
+    println!("Solved: {}/{} ({:.1}%)", solved, positions.len(), solved as f64 * 100.0 / positions.len().max(1) as f64);
+    println!("Failed: {}", failed.join(" "));
+    println!("Time: {}ms", start.elapsed().as_millis());
+}
+
+
+fn split_operations(text: &str) -> Result<Vec<(String, Vec<String>)>, String> {
+    //! Split the operations of an EPD line into their opcode and operands.
+    //!
+    //! Operations end with a ``;``, operands are separated by spaces unless they are quoted strings.
+
+    let mut operations = Vec::new();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(chr) = chars.next() {
+        match chr {
+            ';' => {
+                if !tokens.is_empty() {
+                    let opcode = tokens.remove(0);
+                    operations.push((opcode, std::mem::take(&mut tokens)));
+                }
+            },
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(chr) => string.push(chr),
+                        None => return Err(format!("Unterminated string in `{}`", text.trim())),
+                    }
+                }
+                tokens.push(string);
+            },
+            _ if chr.is_whitespace() => {},
+            _ => {
+                let mut token = String::from(chr);
+                while let Some(chr) = chars.next_if(|chr| !chr.is_whitespace() && *chr != ';' && *chr != '"') {
+                    token.push(chr);
+                }
+                tokens.push(token);
+            },
+        }
+    }
+
+    // the last operation may lack its ``;``
+    if !tokens.is_empty() {
+        let opcode = tokens.remove(0);
+        operations.push((opcode, tokens));
+    }
+
+    Ok(operations)
+}
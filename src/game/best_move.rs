@@ -2,149 +2,578 @@
 //! 
 //! All functions are in an impl for Game.
 
-use crate::piece::{ get_all_piece_moves };
-use crate::consts::{ Color, PieceType, CHECK_MATE_SCORE };
+use crate::consts::{ Color, PieceType, Bound, CHECK_MATE_SCORE, MATE_BOUND, MAX_PLY, MAX_SEARCH_DEPTH };
 use crate::move_struct::{ Move };
-use crate::utils::{ with_offsets };
+use crate::bitboard::{ KNIGHT_ATTACKS, KING_ATTACKS, PAWN_ATTACKS, rook_attacks, bishop_attacks, square_index };
 use std::thread;
+use std::cmp::{ Reverse };
+use crate::search::{ SearchContext, SearchThread, RootMove, MAX_SKILL_LEVEL };
+use std::sync::atomic::{ AtomicI64, AtomicUsize, Ordering };
+use crate::transposition_table::{ TranspositionTable };
 use crate::openings::{ OpeningsDatabase };
+use crate::move_ordering::{ is_losing_capture };
 use rand::Rng;
 use crate::game::{ Game };
-use crate::types::{ Cord };
+use crate::types::{ Cord, Bitboard };
+
+
+/// Centipawns a move may be worse than the best move to be played, per skill level below the maximum.
+const SKILL_MARGIN: i64 = 15;
+/// Centipawns a capture in the quiescence search may fall short of alpha, and still be searched.
+const DELTA_MARGIN: i64 = 200;
+/// Centipawns the first window around the score of the last iteration is wide, on both sides.
+const ASPIRATION_WINDOW: i64 = 25;
+/// Centipawns per ply of depth the static score may be off, for futility pruning.
+const FUTILITY_MARGIN: i64 = 150;
+/// Deepest depth at which positions and moves are pruned by their static score.
+const FUTILITY_DEPTH: u8 = 3;
+/// Shallowest depth at which the null move is tried.
+const NULL_MOVE_DEPTH: u8 = 3;
+/// Shallowest depth, and first move, at which late moves are reduced.
+const LMR_DEPTH: u8 = 3;
+const LMR_MOVE: usize = 4;
 
 
 impl Game {
     //! Best move implementations
 
-    pub fn square_is_attacked(&self, square: Cord, color: Color) -> bool {
-        //! Returns true if the square at the given coordinate is under attack from the specified color.
+    pub fn attackers(&self, square: Cord, color: Color) -> Bitboard {
+        //! Returns the bitboard of the pieces of the specified color that attack the square at the given coordinate.
 
-        let other_color = if color == Color::White { Color::Black } else { Color::White };
-        for piece_type in [PieceType::Knight, PieceType::Rook, PieceType::Bishop, PieceType::Pawn, PieceType::Queen] {
-            for mve in get_all_piece_moves(piece_type, other_color, square, self).iter() {
-                let is_attacked = match self.board[mve.to[1]][mve.to[0]] {
-                    Some(p) => { p.piece_type == piece_type},
-                    None => false,
-                };
-                if is_attacked { return true };
-            }
-        }
+        self.attackers_to(square, self.occupied()) & self.colors[color as usize]
+    }
 
-        for mve in with_offsets(&Color::White, square, self.board, vec![[-1, 0], [1, 0], [-1, -1], [1, -1], [-1, 1], [1, 1], [0, -1], [0, 1]], true).iter() {
-            let is_attacked = match self.board[mve.to[1]][mve.to[0]] {
-                Some(p) => { p.piece_type == PieceType::King },
-                None => false,
-            };
-            if is_attacked { return true };
-        }
+    pub fn attackers_to(&self, square: Cord, occupied: Bitboard) -> Bitboard {
+        //! Returns the bitboard of the pieces of both colors that attack the square, when only the ``occupied`` squares block the sliding pieces.
+        //! 
+        //! Pieces that aren't in ``occupied`` are still returned, the caller has to mask them out.
+
+        let square = square_index(square);
+        let bishops = self.pieces[PieceType::Bishop as usize] | self.pieces[PieceType::Queen as usize];
+        let rooks = self.pieces[PieceType::Rook as usize] | self.pieces[PieceType::Queen as usize];
 
-        false
+        (PAWN_ATTACKS[Color::Black as usize][square] & self.pieces_of(PieceType::Pawn, Color::White))
+            | (PAWN_ATTACKS[Color::White as usize][square] & self.pieces_of(PieceType::Pawn, Color::Black))
+            | (KNIGHT_ATTACKS[square] & self.pieces[PieceType::Knight as usize])
+            | (KING_ATTACKS[square] & self.pieces[PieceType::King as usize])
+            | (bishop_attacks(square, occupied) & bishops)
+            | (rook_attacks(square, occupied) & rooks)
     }
 
-    pub fn get_best_move(&self, depth: u8, opening_database: &OpeningsDatabase) -> Move {
+    pub fn square_is_attacked(&self, square: Cord, color: Color) -> bool {
+        //! Returns true if the square at the given coordinate is under attack from the specified color.
+
+        self.attackers(square, color) != 0
+    }
+
+    pub fn get_best_move(&self, search: &SearchContext, opening_database: &OpeningsDatabase) -> Option<Move> {
         //! Returns a move either from the openings database or from the Minimax algorithm
         //! 
         //! If there is a opening to be played it will go for that option.
-        //! Otherwise it will spawn threads to calculate the best move using the Minimax algorithm.
+        //! Otherwise it searches one ply deeper each iteration (iterative deepening) until the limits of the search are reached.
+        //! Returns None if the game is over (checkmate or stalemate).
+
+        self.get_best_moves(search, opening_database).first().map(|root_move| root_move.mve)
+    }
+
+    pub fn get_best_moves(&self, search: &SearchContext, opening_database: &OpeningsDatabase) -> Vec<RootMove> {
+        //! Like ``get_best_move``, but returns all the searched moves with their score and principal variation.
+        //! 
+        //! The move to play comes first, the other moves follow from best to worst.
+        //! Without legal moves the result is empty.
+        //! A move from the openings database is returned on its own, the book isn't used when analysing multiple variations.
 
         // check for move from opening database
-        if self.moves.len() == self.fullmove_counter {
+        let use_book = search.options.own_book && search.options.multi_pv <= 1 && search.limits.searchmoves.is_empty();
+        if use_book && self.moves.len() == self.fullmove_counter {
             if let Some(mve) = opening_database.find_opening(&self.moves) {
-                return mve;
+                if let Some(legal_mve) = self.find_legal_move(&mve.long_algebraic_notation()) {
+                    return vec![RootMove { mve: legal_mve, score: 0, bound: Bound::Exact, pv: vec![legal_mve] }];
+                }
             }
         }
 
-        // spawn threads
-        let all_moves = self.get_all_moves(self.on_turn);
-        let mut threads: Vec<thread::JoinHandle<(i64, Move)>> = Vec::new();
-        for mve in all_moves.iter() {
-            let mut new_game = self.clone();
-            new_game.do_move(mve);
-            threads.push(
-                thread::spawn(move || {
-                    let r = new_game.private_get_best_move(depth - 1, depth, CHECK_MATE_SCORE);
-                    (-r.0, r.1)
-                })
-            );
-        }
-
-        // receive moves from threads
-        let mut best_moves: Vec<(Move, i64)> = Vec::new();
-        for (idx, t) in threads.into_iter().enumerate() {
-            let result = t.join().unwrap();
-            let mve = all_moves[idx];
-
-            // println!("{} -> {} next expected move: {}", mve.repr(), result.0, result.1.repr());
-
-            let mut insert_at = best_moves.len();
-            for (i, item) in best_moves.to_vec().into_iter().enumerate() {
-                if result.0 > item.1 {
-                    insert_at = i;
-                    break;
-                }
-            }
-            best_moves.insert(insert_at, (mve, result.0));
+        let mut best_moves = self.search_best_moves(search);
+        if best_moves.is_empty() {
+            return best_moves;
+        }
+
+        choose_root_move(&mut best_moves, search.options.skill_level);
+
+        best_moves
+    }
+
+    #[allow(dead_code)]
+    pub fn analyse(&self, search: &SearchContext) -> Vec<RootMove> {
+        //! Returns the best ``MultiPV`` moves from best to worst, each with its score and principal variation.
+        //! 
+        //! The scores are exact when analysing multiple variations, with a single variation only the best score is.
+
+        let mut best_moves = self.search_best_moves(search);
+        best_moves.truncate(search.options.multi_pv.max(1));
+
+        best_moves
+    }
+
+    fn search_best_moves(&self, search: &SearchContext) -> Vec<RootMove> {
+        //! Search one ply deeper each iteration (iterative deepening) until the limits of the search are reached.
+        //! 
+        //! Returns the legal moves sorted from best to worst, or nothing if the game is over.
+        //! With multiple variations or a lower skill level every move is searched with the full window, so all the scores are exact.
+        //! Otherwise only the score of the best move is exact, the others are upper bounds.
+
+        // with only one move there is nothing to think about in a timed game
+        let mut best_moves: Vec<RootMove> = self.legal_moves().into_iter()
+            .filter(|mve| search.limits.searchmoves.is_empty() || search.limits.searchmoves.contains(mve))
+            .map(|mve| RootMove { mve, score: -CHECK_MATE_SCORE, bound: Bound::Exact, pv: vec![mve] })
+            .collect();
+        if best_moves.is_empty() {
+            search.report_game_over(self.is_in_check(self.on_turn));
+            return best_moves;
         }
+        if best_moves.len() == 1 && search.time_manager.hard_limit().is_some() {
+            return best_moves;
+        }
+
+        // a lower skill level doesn't look as far ahead
+        let skill_level = search.options.skill_level.min(MAX_SKILL_LEVEL);
+        let mut max_depth = search.limits.depth.unwrap_or(MAX_SEARCH_DEPTH).clamp(1, MAX_SEARCH_DEPTH);
+        if skill_level < MAX_SKILL_LEVEL {
+            max_depth = max_depth.min(1 + skill_level / 4);
+        }
+
+        let exact_scores = search.options.multi_pv > 1 || skill_level < MAX_SKILL_LEVEL;
+        search.transposition_table.new_search();
+        for depth in 1..=max_depth {
+            let results = self.search_root_moves(search, depth, &best_moves, exact_scores);
+
+            // an iteration that was stopped halfway is thrown away
+            if results.iter().any(|result| result.is_none()) {
+                break;
+            }
 
-        // calculate best move(s) with same highest score
-        let mut same_score = 1;
-        for mve in best_moves.iter().skip(1) {
-            if mve.1 < best_moves[0].1 {
+            for (root_move, result) in best_moves.iter_mut().zip(results) {
+                let (score, bound, pv) = result.unwrap();
+                root_move.score = score;
+                root_move.bound = bound;
+                root_move.pv = self.legal_line(&pv);
+            }
+            sort_root_moves(&mut best_moves);
+            search.report_iteration(depth, &best_moves);
+
+            // searching deeper won't find a faster mate
+            let mate_found = best_moves[0].score.abs() >= MATE_BOUND && !search.limits.infinite;
+            if mate_found || search.stopped() || !search.time_manager.can_start_iteration() {
                 break;
             }
-            same_score += 1;
         }
 
-        let move_idx = rand::thread_rng().gen_range(0..same_score);
+        best_moves
+    }
+
+    pub fn ponder_move(&self, root_move: &RootMove, transposition_table: &TranspositionTable) -> Option<Move> {
+        //! Returns the expected reply to the root move, to think about while the opponent is thinking.
+        //! 
+        //! Taken from the principal variation, or from the transposition table if the variation stops after the move.
+
+        if let Some(mve) = root_move.pv.get(1) {
+            return Some(*mve);
+        }
+
+        let mut new_game = self.clone();
+        new_game.do_move(&root_move.mve);
+        let hash_move = transposition_table.probe(new_game.hash, 0)?.best_move?;
+        new_game.legal_moves().into_iter().find(|mve| *mve == hash_move)
+    }
+
+    fn search_root_moves(&self, search: &SearchContext, depth: u8, root_moves: &[RootMove], exact_scores: bool) -> Vec<Option<(i64, Bound, Vec<Move>)>> {
+        //! Search all the root moves to the specified depth, returning the score, bound and principal variation of each move.
+        //! 
+        //! The search threads take the next move as soon as they are done with one.
+        //! Moves that weren't searched completely because the search was stopped get None.
+        //! 
+        //! Without ``exact_scores`` this is a principal variation search: the first move (the best move of the last iteration) is searched
+        //! with an aspiration window, the other moves with a null window that only proves they are worse.
+        //! A move that turns out to be better is searched again to get its exact score.
+
+        // the first iteration can't be stopped, so there always is a move to play
+        let abortable = depth > 1;
+        let next_move = AtomicUsize::new(0);
+        let best_score = AtomicI64::new(-CHECK_MATE_SCORE);
+        let n_threads = search.options.threads.clamp(1, root_moves.len().max(1));
+        let mut results = vec![None; root_moves.len()];
+
+        if !exact_scores {
+            let mut game = self.clone();
+            let mut search_thread = SearchThread::new(search, abortable);
+            search.report_current_move(&root_moves[0].mve, 1);
+            results[0] = game.search_aspiration_window(&root_moves[0], depth, &mut search_thread);
+            search_thread.finish();
+            match &results[0] {
+                Some((score, _, _)) => best_score.store(*score, Ordering::Relaxed),
+                None => return results,
+            }
+            next_move.store(1, Ordering::Relaxed);
+        }
+
+        thread::scope(|scope| {
+            let threads: Vec<_> = (0..n_threads).map(|_| scope.spawn(|| {
+                let mut game = self.clone();
+                let mut search_thread = SearchThread::new(search, abortable);
+                let mut thread_results = Vec::new();
+                loop {
+                    let idx = next_move.fetch_add(1, Ordering::Relaxed);
+                    if idx >= root_moves.len() || search_thread.stopped() {
+                        break;
+                    }
+
+                    let mve = root_moves[idx].mve;
+                    search.report_current_move(&mve, idx + 1);
+                    let undo = game.make_move(&mve);
+                    let (score, bound) = if exact_scores {
+                        (-game.private_get_best_move(depth - 1, 1, -CHECK_MATE_SCORE, CHECK_MATE_SCORE, &mut search_thread).0, Bound::Exact)
+                    } else {
+                        // prove the move is worse than the best move so far, or search it again to find out how much better it is
+                        let alpha = best_score.load(Ordering::Relaxed);
+                        let mut score = -game.private_get_best_move(depth - 1, 1, -alpha - 1, -alpha, &mut search_thread).0;
+                        if score > alpha && !search_thread.stopped() {
+                            score = -game.private_get_best_move(depth - 1, 1, -CHECK_MATE_SCORE, -alpha, &mut search_thread).0;
+                        }
+                        best_score.fetch_max(score, Ordering::Relaxed);
+                        (score, if score > alpha { Bound::Exact } else { Bound::Upper })
+                    };
+                    game.unmake_move(&mve, &undo);
+                    if search_thread.stopped() {
+                        break;
+                    }
+
+                    let mut pv = vec![mve];
+                    if bound == Bound::Exact {
+                        pv.extend_from_slice(search_thread.pv(1));
+                    }
+                    thread_results.push((idx, score, bound, pv));
+                }
+                search_thread.finish();
+
+                thread_results
+            })).collect();
+
+            for t in threads {
+                for (idx, score, bound, pv) in t.join().unwrap() {
+                    results[idx] = Some((score, bound, pv));
+                }
+            }
+        });
+
+        results
+    }
+
+    fn search_aspiration_window(&mut self, root_move: &RootMove, depth: u8, search: &mut SearchThread) -> Option<(i64, Bound, Vec<Move>)> {
+        //! Search the root move with a small window around its score of the last iteration, which cuts off more than the full window.
+        //! 
+        //! When the score falls outside of the window, the window is made wider on that side and the move is searched again.
+        //! Returns None if the search was stopped.
+
+        let mve = root_move.mve;
+        let mut delta = ASPIRATION_WINDOW;
+        let (mut alpha, mut beta) = if root_move.score.abs() < MATE_BOUND {
+            (root_move.score - delta, root_move.score + delta)
+        } else {
+            (-CHECK_MATE_SCORE, CHECK_MATE_SCORE)
+        };
+
+        let undo = self.make_move(&mve);
+        let score = loop {
+            let score = -self.private_get_best_move(depth - 1, 1, -beta, -alpha, search).0;
+            if search.stopped() {
+                self.unmake_move(&mve, &undo);
+                return None;
+            }
+
+            if score <= alpha {
+                alpha = score.saturating_sub(delta).max(-CHECK_MATE_SCORE);
+            } else if score >= beta {
+                beta = score.saturating_add(delta);
+            } else {
+                break score;
+            }
+            delta = delta.saturating_mul(2);
+        };
+        self.unmake_move(&mve, &undo);
 
-        best_moves[move_idx].0
+        let mut pv = vec![mve];
+        pv.extend_from_slice(search.pv(1));
+        Some((score, Bound::Exact, pv))
     }
 
-    fn private_get_best_move(&self, depth: u8, maximum_depth: u8, score_to_beat: i64) -> (i64, Move) {
+    fn private_get_best_move(&mut self, depth: u8, ply: usize, mut alpha: i64, beta: i64, search: &mut SearchThread) -> (i64, Option<Move>) {
         //! Function to calculate best move.
         //! 
         //! Each spawned thread by the function ``get_best_move`` runs this function for it's sub-game.
-        //! The Minimax algorithm continues in this function.
+        //! The Minimax algorithm (in its negamax form with alpha-beta pruning) continues in this function,
+        //! making and unmaking the moves on the sub-game instead of copying it.
+        //! 
+        //! The returned score is from the point of view of the color on turn. Mate scores count the plies from the root.
+        //! Without legal moves the position is checkmate (when in check) or stalemate, and there is no best move.
+        //! When the search is stopped the returned score is meaningless.
 
-        let all_moves = self.get_all_moves(self.on_turn);
+        let mut best_move = None;
+        search.count_node(ply);
+        search.clear_pv(ply);
+        if search.stopped() {
+            return (0, best_move);
+        }
+        if self.is_draw(ply) {
+            return (search.context.draw_score(self.on_turn), best_move);
+        }
 
-        let mut highest_score: i64 = -CHECK_MATE_SCORE;
-        let mut best_move = Move::from_long_algebraic_notation(String::from("a1a2"));
-        for mve in all_moves.iter() {
-            // generate new game from move
-            let mut new_game = self.clone();
-            new_game.do_move(mve);
+        // a check is searched a ply deeper, so the search doesn't end before the check is answered
+        let color = self.on_turn;
+        let options = &search.context.options;
+        let in_check = self.is_in_check(color);
+        let depth = if in_check && options.check_extensions { depth + 1 } else { depth };
+        if depth == 0 || ply >= MAX_PLY {
+            return (self.quiescence(ply, alpha, beta, search), best_move);
+        }
 
-            // calculate the score of the game
-            let mut game_score: i64;
-            game_score = -new_game.get_board_score(new_game.on_turn);
-            if game_score == -CHECK_MATE_SCORE || game_score == CHECK_MATE_SCORE {
-                return (game_score, *mve);
+        // look up the position in the transposition table
+        let mut hash_move = None;
+        if let Some(entry) = search.context.transposition_table.probe(self.hash, ply) {
+            hash_move = entry.best_move;
+            if entry.depth >= depth {
+                let usable = match entry.bound {
+                    Bound::Exact => true,
+                    Bound::Lower => entry.score >= beta,
+                    Bound::Upper => entry.score <= alpha,
+                };
+                if usable {
+                    return (entry.score, entry.best_move);
+                }
             }
-            if depth > 1 {
-                let r = new_game.private_get_best_move(depth - 1, maximum_depth, -highest_score);
-                game_score = -r.0;
+        }
+
+        // the selective search only prunes positions that aren't on the principal variation and have no mate in sight
+        let is_pv = alpha + 1 < beta;
+        let static_score = self.get_board_score(color);
+        let can_prune = !is_pv && !in_check && alpha.abs() < MATE_BOUND && beta.abs() < MATE_BOUND;
+
+        // reverse futility pruning: the position is so good that searching it won't bring the score below beta
+        if can_prune && options.futility_pruning && depth <= FUTILITY_DEPTH && static_score - FUTILITY_MARGIN * depth as i64 >= beta {
+            return (static_score, best_move);
+        }
+
+        // null move pruning: if the position is still good enough after passing, a real move will be as well.
+        // That isn't true in zugzwang, which happens mostly when there are only pawns left, so it isn't tried then.
+        // Two null moves in a row can't happen, the static score is below beta for the opponent.
+        let pieces = self.colors[color as usize] & !self.pieces[PieceType::Pawn as usize] & !self.pieces[PieceType::King as usize];
+        if can_prune && options.null_move && depth >= NULL_MOVE_DEPTH && static_score >= beta && pieces != 0 {
+            let reduction = 2 + depth / 6;
+            let undo = self.make_null_move();
+            let score = -self.private_get_best_move(depth.saturating_sub(1 + reduction), ply + 1, -beta, -beta + 1, search).0;
+            self.unmake_null_move(&undo);
+            if search.stopped() {
+                return (0, None);
+            }
+            if score >= beta {
+                // a mate after passing isn't proven
+                return (if score >= MATE_BOUND { beta } else { score }, best_move);
             }
+        }
+
+        let mut all_moves = self.get_all_moves(self.on_turn);
+        search.ordering.sort_moves(self, &mut all_moves, hash_move, ply);
 
-            // check if this is the best performing one
-            if game_score > CHECK_MATE_SCORE - maximum_depth as i64 {
-                game_score -= 1;
-            } else if game_score < -CHECK_MATE_SCORE + maximum_depth as i64 {
-                game_score += 1;
+        let original_alpha = alpha;
+        let mut highest_score: i64 = -CHECK_MATE_SCORE;
+        let mut n_legal_moves = 0;
+        let mut tried_quiets = Vec::new();
+        for mve in all_moves.iter() {
+            // do the move, unless it leaves the own king attacked
+            let is_quiet = !self.is_capture_or_promotion(mve);
+            search.clear_pv(ply + 1);
+            let undo = self.make_move(mve);
+            if self.is_in_check(color) {
+                self.unmake_move(mve, &undo);
+                continue;
             }
+            n_legal_moves += 1;
+            let gives_check = self.is_in_check(color.other());
+            let is_late_quiet = n_legal_moves > 1 && is_quiet && !gives_check;
 
-            // ab-pruning
-            if game_score > score_to_beat {
-                return (game_score, *mve);
+            // futility pruning: a quiet move can't raise the score enough to get above alpha
+            let futility_score = static_score + FUTILITY_MARGIN * depth as i64;
+            if can_prune && options.futility_pruning && is_late_quiet && depth <= FUTILITY_DEPTH && futility_score <= alpha {
+                self.unmake_move(mve, &undo);
+                highest_score = highest_score.max(futility_score);
+                tried_quiets.push(*mve);
+                continue;
+            }
+
+            // late move reductions: the later a quiet move comes in the move ordering, the less deep it is searched
+            let reduction = if options.late_move_reductions && !in_check && is_late_quiet && depth >= LMR_DEPTH && n_legal_moves >= LMR_MOVE {
+                (((depth as f64).ln() * (n_legal_moves as f64).ln() / 2.0) as u8).min(depth - 2)
+            } else {
+                0
+            };
+
+            // calculate the score of the game, the moves after the first only have to be proven worse (principal variation search)
+            let game_score = if n_legal_moves == 1 {
+                -self.private_get_best_move(depth - 1, ply + 1, -beta, -alpha, search).0
+            } else {
+                let mut score = -self.private_get_best_move(depth - 1 - reduction, ply + 1, -alpha - 1, -alpha, search).0;
+                if score > alpha && reduction > 0 && !search.stopped() {
+                    score = -self.private_get_best_move(depth - 1, ply + 1, -alpha - 1, -alpha, search).0;
+                }
+                if score > alpha && score < beta && !search.stopped() {
+                    score = -self.private_get_best_move(depth - 1, ply + 1, -beta, -alpha, search).0;
+                }
+                score
+            };
+
+            // take the move back
+            self.unmake_move(mve, &undo);
+
+            // don't use (or store) the results of an unfinished search
+            if search.stopped() {
+                return (0, None);
             }
 
             // update highest score
-            if game_score > highest_score {
+            if best_move.is_none() || game_score > highest_score {
                 highest_score = game_score;
-                best_move = *mve;
+                best_move = Some(*mve);
+            }
+
+            // ab-pruning
+            if highest_score > alpha {
+                search.update_pv(ply, *mve);
+                alpha = highest_score;
+            }
+            if alpha >= beta {
+                search.count_cutoff(n_legal_moves == 1);
+                if is_quiet {
+                    search.ordering.update(self, mve, ply, depth, &tried_quiets);
+                }
+                break;
             }
+            if is_quiet {
+                tried_quiets.push(*mve);
+            }
+        }
+
+        // no legal moves: checkmate or stalemate
+        if best_move.is_none() {
+            highest_score = if in_check { -(CHECK_MATE_SCORE - ply as i64) } else { search.context.draw_score(color) };
         }
 
+        let bound = if best_move.is_none() {
+            Bound::Exact
+        } else if highest_score >= beta {
+            Bound::Lower
+        } else if highest_score > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        let stored_move = if bound == Bound::Upper { None } else { best_move };
+        search.context.transposition_table.store(self.hash, depth, bound, highest_score, ply, stored_move);
+
         (highest_score, best_move)
     }
+
+    pub fn quiescence(&mut self, ply: usize, mut alpha: i64, beta: i64, search: &mut SearchThread) -> i64 {
+        //! Search the captures and promotions at the end of the main search, until the position is quiet.
+        //! 
+        //! Without it the search would stop in the middle of an exchange and score the position as if the piece that was just taken isn't taken back.
+        //! The side on turn doesn't have to capture, so the static score is a lower bound (stand pat).
+        //! Captures that lose material in the static exchange evaluation aren't searched.
+        //! When in check all the moves are searched, so a mate is recognised.
+
+        search.count_qnode(ply);
+        search.clear_pv(ply);
+        if search.stopped() {
+            return 0;
+        }
+
+        let color = self.on_turn;
+        let in_check = self.is_in_check(color);
+        let stand_pat = self.get_board_score(color);
+        if ply >= MAX_PLY {
+            return stand_pat;
+        }
+        if !in_check {
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+
+        let mut moves: Vec<Move> = self.get_all_moves(color).into_iter()
+            .filter(|mve| in_check || self.is_capture_or_promotion(mve))
+            .collect();
+        let scores = search.ordering.sort_moves(self, &mut moves, None, ply);
+
+        let mut highest_score = if in_check { -CHECK_MATE_SCORE } else { stand_pat };
+        let mut has_legal_move = false;
+        for (mve, score) in moves.iter().zip(scores) {
+            // the losing captures are sorted last, so all the moves that are left lose material
+            if !in_check && is_losing_capture(score) {
+                break;
+            }
+            // a capture that can't bring the score close to alpha isn't worth searching (delta pruning)
+            if !in_check && stand_pat + self.capture_gain(mve) + DELTA_MARGIN <= alpha {
+                continue;
+            }
+
+            search.clear_pv(ply + 1);
+            let undo = self.make_move(mve);
+            if self.is_in_check(color) {
+                self.unmake_move(mve, &undo);
+                continue;
+            }
+            has_legal_move = true;
+
+            let score = -self.quiescence(ply + 1, -beta, -alpha, search);
+            self.unmake_move(mve, &undo);
+            if search.stopped() {
+                return 0;
+            }
+
+            highest_score = highest_score.max(score);
+            if score > alpha {
+                search.update_pv(ply, *mve);
+                alpha = score;
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        if in_check && !has_legal_move {
+            return -(CHECK_MATE_SCORE - ply as i64);
+        }
+
+        highest_score
+    }
+}
+
+
+pub fn sort_root_moves(root_moves: &mut [RootMove]) {
+    //! Sort the root moves from best to worst, an exact score goes before an upper bound of the same score.
+
+    root_moves.sort_by_key(|root_move| (Reverse(root_move.score), root_move.bound != Bound::Exact));
+}
+
+pub fn choose_root_move(root_moves: &mut Vec<RootMove>, skill_level: u8) {
+    //! Move the root move to play to the front of the sorted root moves.
+    //!
+    //! At full strength that is the best move, a lower skill level also picks exact moves that are a bit worse.
+
+    let margin = (MAX_SKILL_LEVEL - skill_level.min(MAX_SKILL_LEVEL)) as i64 * SKILL_MARGIN;
+    let lowest_score = root_moves[0].score.saturating_sub(margin);
+    // the best move is always a candidate, whatever its bound
+    let candidates = 1 + root_moves[1..].iter().take_while(|root_move| root_move.score >= lowest_score && root_move.bound == Bound::Exact).count();
+    let move_idx = rand::thread_rng().gen_range(0..candidates);
+    let chosen_move = root_moves.remove(move_idx);
+    root_moves.insert(0, chosen_move);
 }
@@ -0,0 +1,70 @@
+//! Functions for changing the pieces on the board, keeping the board and the bitboards in sync.
+//! 
+//! All functions are in an impl for Game.
+
+use crate::piece::{ Piece };
+use crate::consts::{ Color, PieceType };
+use crate::bitboard::{ cord_bitboard, square_index };
+use crate::zobrist::{ piece_key };
+use crate::game::{ Game };
+use crate::types::{ Cord, Bitboard };
+
+
+impl Game {
+    //! Board implementations
+
+    pub fn put_piece(&mut self, cord: Cord, piece: Piece) {
+        //! Place the piece on the specified (empty) square.
+
+        let bit = cord_bitboard(cord);
+        self.board[cord[1]][cord[0]] = Some(piece);
+        self.pieces[piece.piece_type as usize] |= bit;
+        self.colors[piece.color as usize] |= bit;
+        self.hash ^= piece_key(&piece, square_index(cord));
+    }
+
+    pub fn remove_piece(&mut self, cord: Cord) -> Option<Piece> {
+        //! Remove the piece from the specified square and return it.
+
+        let piece = self.board[cord[1]][cord[0]].take();
+        if let Some(p) = piece {
+            let bit = cord_bitboard(cord);
+            self.pieces[p.piece_type as usize] &= !bit;
+            self.colors[p.color as usize] &= !bit;
+            self.hash ^= piece_key(&p, square_index(cord));
+        }
+
+        piece
+    }
+
+    pub fn calculate_bitboards(&mut self) {
+        //! Calculates the bitboards from the board.
+        //! 
+        //! Only used when loading the board.
+        //! Afterwards the bitboards are updated together with the board by ``put_piece`` and ``remove_piece``.
+
+        self.pieces = [0; 6];
+        self.colors = [0; 2];
+        for y in 0..8 {
+            for x in 0..8 {
+                if let Some(p) = self.board[y][x] {
+                    let bit = cord_bitboard([x, y]);
+                    self.pieces[p.piece_type as usize] |= bit;
+                    self.colors[p.color as usize] |= bit;
+                }
+            }
+        }
+    }
+
+    pub fn occupied(&self) -> Bitboard {
+        //! Bitboard of all the squares with a piece on it.
+
+        self.colors[Color::White as usize] | self.colors[Color::Black as usize]
+    }
+
+    pub fn pieces_of(&self, piece_type: PieceType, color: Color) -> Bitboard {
+        //! Bitboard of the pieces of the specified type and color.
+
+        self.pieces[piece_type as usize] & self.colors[color as usize]
+    }
+}
@@ -0,0 +1,81 @@
+//! Functions to recognise draws by repetition, the fifty-move rule and insufficient material.
+//! 
+//! All functions are in an impl for Game.
+
+use crate::consts::{ PieceType };
+use crate::game::{ Game };
+use crate::types::{ Bitboard };
+
+
+/// The dark squares (a1, c1, b2, ...).
+const DARK_SQUARES: Bitboard = 0xaa55_aa55_aa55_aa55;
+
+
+impl Game {
+    //! Draw implementations
+
+    pub fn repetitions(&self) -> usize {
+        //! Returns how many times the current position occurred before.
+        //! 
+        //! Only positions with the same color on turn since the last capture or pawn move can be the same.
+
+        self.hash_history.iter().rev().take(self.halfmove_clock).skip(1).step_by(2).filter(|hash| **hash == self.hash).count()
+    }
+
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetitions() >= 2
+    }
+
+    pub fn is_fifty_move_draw(&self) -> bool {
+        //! Returns true if there were fifty moves of each side without a capture or pawn move.
+
+        self.halfmove_clock >= 100
+    }
+
+    pub fn is_seventy_five_move_draw(&self) -> bool {
+        //! Returns true if there were seventy-five moves of each side without a capture or pawn move,
+        //! which ends the game without either player claiming the draw.
+
+        self.halfmove_clock >= 150
+    }
+
+    pub fn is_insufficient_material(&self) -> bool {
+        //! Returns true if neither side has enough material left to mate:
+        //! only kings, a single knight or bishop, or bishops that are all on squares of the same color.
+
+        let pieces = |piece_type: PieceType| self.pieces[piece_type as usize];
+        if pieces(PieceType::Pawn) | pieces(PieceType::Rook) | pieces(PieceType::Queen) != 0 {
+            return false;
+        }
+
+        let knights = pieces(PieceType::Knight);
+        let bishops = pieces(PieceType::Bishop);
+        (knights | bishops).count_ones() <= 1
+            || (knights == 0 && (bishops & DARK_SQUARES == 0 || bishops & !DARK_SQUARES == 0))
+    }
+
+    pub fn is_draw(&self, ply: usize) -> bool {
+        //! Returns true if the search should score the position as a draw, the position is ``ply`` plies after the root.
+        //! 
+        //! A position that repeats a position of the search is already a draw, because the same moves can be played again.
+        //! Positions of the game before the root need to occur three times.
+
+        if self.is_fifty_move_draw() || self.is_insufficient_material() {
+            return true;
+        }
+
+        let mut repetitions = 0;
+        for (idx, hash) in self.hash_history.iter().rev().take(self.halfmove_clock).enumerate().skip(1).step_by(2) {
+            // plies between the two positions
+            let distance = idx + 1;
+            if *hash == self.hash {
+                if distance <= ply {
+                    return true;
+                }
+                repetitions += 1;
+            }
+        }
+
+        repetitions >= 2
+    }
+}
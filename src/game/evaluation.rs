@@ -4,9 +4,16 @@
 
 use crate::consts::{ GamePhase };
 use crate::game::{ Game };
-use crate::consts::{ Color, CHECK_MATE_SCORE };
+use crate::consts::{ Color };
 use crate::move_struct::{ Move };
+use crate::piece::{ Piece };
 use crate::consts::{ PieceType };
+use crate::bitboard::{ squares, index_to_cord };
+use crate::types::{ Bitboard };
+
+
+// ranks 3 to 6
+const MIDFIELD: Bitboard = 0x0000_ffff_ffff_0000;
 
 
 impl Game {
@@ -15,18 +22,9 @@ impl Game {
     pub fn calculate_game_phase(&mut self) {
         //! Calculates the current game phase.
 
-        let mut n_pieces_midfield = 0;
-        let mut n_pieces = 0;
-        for y in 0..8 {
-            for x in 0..8 {
-                if self.board[y][x].is_some() {
-                    n_pieces += 1;
-                    if (2..=5).contains(&y) {
-                        n_pieces_midfield += 1;
-                    }
-                }
-            }
-        }
+        let occupied = self.occupied();
+        let n_pieces = occupied.count_ones();
+        let n_pieces_midfield = (occupied & MIDFIELD).count_ones();
 
         if n_pieces_midfield > 8 || self.fullmove_counter > 15 {
             self.game_phase = GamePhase::Mid;
@@ -53,36 +51,14 @@ impl Game {
         //! Afterwards the score is simply updated by only checking the new values for the moved pieces.
         let mut board_score: i64 = 0;
 
-        let mut white_king_present = false;
-        let mut black_king_present = false;
-
-        for (y, rank) in self.board.iter().enumerate() {
-            for (x, piece) in rank.iter().enumerate() {
-                match piece {
-                    Some(p) => {
-                        let piece_score = p.score([x, y], &self.game_phase);
-                        if p.color == Color::White {
-                            board_score += piece_score;
-                        } else {
-                            board_score -= piece_score;
-                        }
-
-                        if p.piece_type == PieceType::King {
-                            match p.color {
-                                Color::White => white_king_present = true,
-                                Color::Black => black_king_present = true,
-                            }
-                        }
-                    },
-                    None => {},
-                }
-            }
-        }
-
-        if !white_king_present || !black_king_present {
-            board_score = CHECK_MATE_SCORE;
-            if !white_king_present {
-                board_score *= -1;
+        for square in squares(self.occupied()) {
+            let cord = index_to_cord(square);
+            let p = self.board[cord[1]][cord[0]].unwrap();
+            let piece_score = p.score(cord, &self.game_phase);
+            if p.color == Color::White {
+                board_score += piece_score;
+            } else {
+                board_score -= piece_score;
             }
         }
 
@@ -93,26 +69,41 @@ impl Game {
         //! Returns a vector of Moves that the specified color can make at this point in the game.
 
         let mut all_moves = Vec::new();
+        let pawns = self.pieces_of(PieceType::Pawn, color);
 
-        for (y, rank) in (&self.board).iter().enumerate() {
-            for (x, piece) in rank.iter().enumerate() {
-                match piece {
-                    Some(p) => if color == p.color {
-                        // sort so that pawns will get checked last
-                        match p.piece_type {
-                            PieceType::Pawn => { all_moves.extend(p.get_all_moves([x, y], self)) },
-                            _ => {
-                                for mve in p.get_all_moves([x, y], self) {
-                                    all_moves.insert(0, mve);
-                                }
-                            }
-                        }
-                    },
-                    None => {},
-                }
-            }
+        // sort so that pawns will get checked last
+        for square in squares(self.colors[color as usize] & !pawns).chain(squares(pawns)) {
+            let cord = index_to_cord(square);
+            let p = self.board[cord[1]][cord[0]].unwrap();
+            all_moves.extend(p.get_all_moves(cord, self));
         }
 
         all_moves
     }
-}
\ No newline at end of file
+
+    pub fn is_capture_or_promotion(&self, mve: &Move) -> bool {
+        //! Returns true if the move takes a piece (en passant included) or promotes a pawn, the moves that aren't quiet.
+
+        let piece = self.board[mve.from[1]][mve.from[0]].unwrap();
+        self.board[mve.to[1]][mve.to[0]].is_some() || (piece.piece_type == PieceType::Pawn && (mve.from[0] != mve.to[0] || mve.piece.is_some()))
+    }
+
+    pub fn capture_gain(&self, mve: &Move) -> i64 {
+        //! Returns the score of the piece the move takes plus the score a promotion adds, zero for a quiet move.
+
+        let piece = self.board[mve.from[1]][mve.from[0]].unwrap();
+        let mut gain = match self.board[mve.to[1]][mve.to[0]] {
+            Some(victim) => victim.score(mve.to, &self.game_phase),
+            // en passant
+            None if piece.piece_type == PieceType::Pawn && mve.from[0] != mve.to[0] => {
+                Piece { piece_type: PieceType::Pawn, color: piece.color.other() }.score([mve.to[0], mve.from[1]], &self.game_phase)
+            },
+            None => 0,
+        };
+        if let (PieceType::Pawn, Some(promotion)) = (piece.piece_type, mve.piece) {
+            gain += Piece { piece_type: promotion.piece_type, color: piece.color }.score(mve.to, &self.game_phase) - piece.score(mve.from, &self.game_phase);
+        }
+
+        gain
+    }
+}
@@ -0,0 +1,50 @@
+//! Functions for the Zobrist hash of the position.
+//! 
+//! All functions are in an impl for Game.
+
+use crate::consts::{ Color, PieceType };
+use crate::bitboard::{ PAWN_ATTACKS, squares, square_index, index_to_cord };
+use crate::zobrist::{ piece_key, side_key, castle_key, en_passant_key };
+use crate::game::{ Game };
+
+
+impl Game {
+    //! Hashing implementations
+
+    pub fn calculate_hash(&self) -> u64 {
+        //! Calculates the hash of the current position from scratch.
+        //! 
+        //! Only used when loading the board (and to check the incremental updates in debug builds).
+        //! Afterwards the hash is updated by ``make_move`` and ``unmake_move``.
+
+        let mut hash = 0;
+        for square in squares(self.occupied()) {
+            let cord = index_to_cord(square);
+            hash ^= piece_key(&self.board[cord[1]][cord[0]].unwrap(), square);
+        }
+
+        if self.on_turn == Color::Black {
+            hash ^= side_key();
+        }
+
+        hash ^ castle_key(&self.castle) ^ self.en_passant_hash()
+    }
+
+    pub fn en_passant_hash(&self) -> u64 {
+        //! Key for the en passant file, but only if the color on turn has a pawn that can take en passant.
+        //! 
+        //! That way positions that only differ in an en passant capture that isn't possible get the same hash.
+
+        match self.en_passant_target_square {
+            Some(cord) => {
+                let pawns = self.pieces_of(PieceType::Pawn, self.on_turn);
+                if PAWN_ATTACKS[self.on_turn.other() as usize][square_index(cord)] & pawns != 0 {
+                    en_passant_key(cord[0])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+}
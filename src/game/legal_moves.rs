@@ -0,0 +1,104 @@
+//! Functions for filtering the generated moves down to the moves that are actually legal.
+//! 
+//! All functions are in an impl for Game.
+
+use crate::consts::{ Color, PieceType };
+use crate::move_struct::{ Move };
+use crate::game::{ Game };
+use crate::types::{ Cord, Bitboard };
+use crate::bitboard::{ index_to_cord };
+
+
+impl Game {
+    //! Legal move implementations
+
+    pub fn king_square(&self, color: Color) -> Option<Cord> {
+        //! Returns the coordinate of the king of the specified color. Or None if there is no such king on the board.
+
+        match self.pieces_of(PieceType::King, color) {
+            0 => None,
+            king => Some(index_to_cord(king.trailing_zeros() as usize)),
+        }
+    }
+
+    pub fn is_in_check(&self, color: Color) -> bool {
+        //! Returns true if the king of the specified color is attacked by the other color.
+
+        match self.king_square(color) {
+            Some(square) => self.square_is_attacked(square, color.other()),
+            None => false,
+        }
+    }
+
+    pub fn is_check(&self) -> bool {
+        //! Returns true if the color that is on turn is in check.
+
+        self.is_in_check(self.on_turn)
+    }
+
+    #[allow(dead_code)]
+    pub fn checkers(&self) -> Bitboard {
+        //! Returns the bitboard of the pieces that give check to the color that is on turn.
+
+        match self.king_square(self.on_turn) {
+            Some(square) => self.attackers(square, self.on_turn.other()),
+            None => 0,
+        }
+    }
+
+    pub fn is_legal(&self, mve: &Move) -> bool {
+        //! Returns true if the color that is on turn can play the move.
+        //!
+        //! The move is matched on its long algebraic notation, so a parsed move (without the castling piece) works too.
+
+        let notation = mve.long_algebraic_notation();
+        let Some(generated) = self.get_all_moves(self.on_turn).into_iter().find(|generated| generated.long_algebraic_notation() == notation) else {
+            return false;
+        };
+
+        let mut new_game = self.clone();
+        new_game.make_move(&generated);
+        !new_game.is_in_check(self.on_turn)
+    }
+
+    pub fn legal_moves(&self) -> Vec<Move> {
+        //! Returns a vector of all the fully legal moves for the color that is on turn.
+        //! 
+        //! Unlike ``get_all_moves`` this filters out every move that leaves the own king attacked
+        //! (moving pinned pieces, ignoring a check, en passant captures that uncover the king etc.).
+
+        let color = self.on_turn;
+        let mut new_game = self.clone();
+        self.get_all_moves(color).into_iter().filter(|mve| {
+            let undo = new_game.make_move(mve);
+            let is_legal = !new_game.is_in_check(color);
+            new_game.unmake_move(mve, &undo);
+            is_legal
+        }).collect()
+    }
+
+    pub fn find_legal_move(&self, notation: &str) -> Option<Move> {
+        //! Returns the legal move matching the specified long algebraic notation (e.g. ``e2e4`` or ``e7e8q``).
+        //! 
+        //! Returns None if there is no such legal move in the current position.
+
+        let notation = notation.to_lowercase();
+        self.legal_moves().into_iter().find(|mve| mve.long_algebraic_notation() == notation)
+    }
+
+    pub fn legal_line(&self, moves: &[Move]) -> Vec<Move> {
+        //! Returns the moves from the start of the line that can legally be played one after another.
+
+        let mut game = self.clone();
+        let mut line = Vec::new();
+        for mve in moves.iter() {
+            if !game.is_legal(mve) {
+                break;
+            }
+            game.do_move(mve);
+            line.push(*mve);
+        }
+
+        line
+    }
+}
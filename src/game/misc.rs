@@ -15,10 +15,7 @@ impl Game {
     pub fn show_board(&self, highlight: Option<Vec<Cord>>, seen_from: Color) {
         //! Will print the board with colors and chess unicode character in the console.
 
-        let highlight = match highlight {
-            Some(h) => h,
-            None => Vec::new(),
-        };
+        let highlight = highlight.unwrap_or_default();
 
         for mut y in 0..8 {
             if seen_from == Color::White { y = 7 - y }
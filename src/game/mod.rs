@@ -5,21 +5,52 @@ pub mod misc;
 pub mod evaluation;
 pub mod moving;
 pub mod best_move;
+pub mod legal_moves;
+pub mod perft;
+pub mod board;
+pub mod hashing;
+pub mod draw;
+pub mod status;
+pub mod see;
 
 use crate::piece::{ Piece };
 use crate::consts::{ Color, GamePhase };
 use crate::move_struct::{ Move };
-use crate::types::{ Cord };
+use crate::types::{ Cord, Bitboard };
 
 
 #[derive(Clone)]
 pub struct Game {
     pub board: [[Option<Piece>; 8]; 8],
+    /// Bitboard per piece type (indexed by ``PieceType as usize``), kept in sync with ``board``.
+    pub pieces: [Bitboard; 6],
+    /// Bitboard per color (indexed by ``Color as usize``), kept in sync with ``board``.
+    pub colors: [Bitboard; 2],
     pub on_turn: Color,
     pub castle: Vec<Piece>,
     pub en_passant_target_square: Option<Cord>,
     pub moves: Vec<Move>,
     pub score_white: i64,
+    /// Zobrist hash of the position, kept up to date by ``make_move``.
+    pub hash: u64,
+    /// Hashes of the positions before each of the ``moves``, for finding repetitions.
+    pub hash_history: Vec<u64>,
+    /// Plies since the last capture or pawn move.
+    pub halfmove_clock: usize,
     pub fullmove_counter: usize,
     pub game_phase: GamePhase,
 }
+
+impl PartialEq for Game {
+    fn eq(&self, other: &Game) -> bool {
+        //! Games are equal when everything a FEN string describes is equal, the moves that led to the position don't matter.
+
+        self.board == other.board
+            && self.on_turn == other.on_turn
+            && self.castle.len() == other.castle.len()
+            && self.castle.iter().all(|piece| other.castle.contains(piece))
+            && self.en_passant_target_square == other.en_passant_target_square
+            && self.halfmove_clock == other.halfmove_clock
+            && self.fullmove_counter == other.fullmove_counter
+    }
+}
@@ -6,11 +6,38 @@ use crate::piece::{ Piece };
 use crate::consts::{ Color, PieceType, MoveType };
 use crate::move_struct::{ Move };
 use crate::game::{ Game };
+use crate::types::{ Cord };
+use crate::zobrist::{ castle_key, side_key };
+
+
+/// Everything needed to take back a move made with ``Game::make_move``.
+#[derive(Clone, Copy)]
+pub struct UndoMove {
+    move_type: MoveType,
+    castle_piece: Option<Piece>,
+    piece: Piece,
+    take_piece: Option<Piece>,
+    take_piece_cord: Cord,
+    castle: [Option<Piece>; 4],
+    en_passant_target_square: Option<Cord>,
+    score_white: i64,
+    hash: u64,
+    halfmove_clock: usize,
+    fullmove_counter: usize,
+}
+
+/// What ``make_null_move`` changes, so ``unmake_null_move`` can restore it.
+pub struct UndoNullMove {
+    en_passant_target_square: Option<Cord>,
+    hash: u64,
+    halfmove_clock: usize,
+}
 
 
 impl Game {
     //! Move implementations
 
+    #[allow(dead_code)]
     pub fn apply_moves(&mut self, moves: &[Move]) {
         //! When supplied with a vector of Moves, this function will apply all moves in order.
 
@@ -22,117 +49,104 @@ impl Game {
     fn disable_castle(&mut self, color: &Color) {
         //! Will disable the castling ability (King's and queen's side) for the specified color.
 
-        let mut idxs_to_remove = Vec::new();
-        for (idx, piece) in self.castle.iter().enumerate() {
-            if piece.color == *color {
-                idxs_to_remove.push(idx);
-            }
-        }
+        self.castle.retain(|piece| piece.color != *color);
+    }
+
+    fn update_castle(&mut self, piece: Piece, cord: Cord) {
+        //! Will disable the castling abilities that are lost when the specified piece leaves (or is taken on) the specified coordinate.
 
-        for (idx, delete_idx) in idxs_to_remove.iter().enumerate() {
-            self.castle.remove(delete_idx - idx);
+        let home_y = if piece.color == Color::White { 0 } else { 7 };
+        if piece.piece_type == PieceType::King {
+            self.disable_castle(&piece.color);
+        } else if piece.piece_type == PieceType::Rook && cord[1] == home_y && (cord[0] == 0 || cord[0] == 7) {
+            let to_remove_piece = Piece { piece_type: if cord[0] == 0 { PieceType::Queen } else { PieceType::King }, color: piece.color };
+            self.castle.retain(|p| *p != to_remove_piece);
         }
     }
 
     pub fn do_move(&mut self, mve: &Move) {
         //! Will apply the specified Move to the game.
+        //! 
+        //! Use ``make_move`` instead if the move has to be taken back later.
+
+        self.make_move(mve);
+    }
+
+    pub fn make_move(&mut self, mve: &Move) -> UndoMove {
+        //! Will apply the specified Move to the game and return the record needed to take it back with ``unmake_move``.
+
+        let mut castle = [None; 4];
+        for (idx, piece) in self.castle.iter().take(4).enumerate() {
+            castle[idx] = Some(*piece);
+        }
+
+        let hash = self.hash;
+        self.hash ^= castle_key(&self.castle) ^ self.en_passant_hash();
 
         let mut score_delta: i64 = 0;
-        self.en_passant_target_square = None;
-        let piece = self.board[mve.from[1]][mve.from[0]].unwrap();
+        let score_white = self.score_white;
+        let halfmove_clock = self.halfmove_clock;
+        let fullmove_counter = self.fullmove_counter;
+        let en_passant_target_square = self.en_passant_target_square.take();
+        let piece = self.remove_piece(mve.from).unwrap();
         let mut take_piece = None;
-        let mut take_piece_cord = [0usize; 2];
+        let mut take_piece_cord = mve.to;
         score_delta -= piece.score(mve.from, &self.game_phase);
-        self.board[mve.from[1]][mve.from[0]] = None;
 
-        let (mve_type, mve_piece) = mve.get_move_type(Some(&self.castle), self.en_passant_target_square, Some(piece.piece_type));
+        let (mve_type, mve_piece) = mve.get_move_type(Some(&self.castle), en_passant_target_square, Some(piece.piece_type));
         match mve_type {
-            MoveType::Standard => {
+            MoveType::Standard | MoveType::Promote => {
+                // the promoted piece always has the color of the pawn
+                let new_piece = match mve_type {
+                    MoveType::Promote => Piece { piece_type: mve_piece.unwrap().piece_type, color: piece.color },
+                    _ => piece,
+                };
+
                 // update score
-                score_delta += piece.score(mve.to, &self.game_phase);
+                score_delta += new_piece.score(mve.to, &self.game_phase);
 
                 // do move
-                // check for disable castle
-                let mut pieces_to_check = vec![piece];
-                if let Some(p) = self.board[mve.to[1]][mve.to[0]] {
-                    pieces_to_check.push(p);
-                    take_piece = Some(p);
-                    take_piece_cord = [mve.to[0], mve.to[1]];
-                }
-                for p in pieces_to_check {
-                    if p.piece_type == PieceType::King {
-                        self.disable_castle(&p.color);
-                    } else if p.piece_type == PieceType::Rook {
-                        let to_remove_piece = Piece { piece_type: if mve.from[0] == 0 { PieceType::Queen } else { PieceType::King }, color: p.color};
-                        if self.castle.contains(&to_remove_piece) {
-                            self.castle.remove(self.castle.iter().position(|x| *x == to_remove_piece).unwrap());
-                        }
-                    }
-                }
-
-                self.board[mve.to[1]][mve.to[0]] = Some(piece);
+                take_piece = self.remove_piece(mve.to);
+                self.put_piece(mve.to, new_piece);
 
-                if piece.piece_type == PieceType::Pawn {
-                    let delta = mve.from[1] as i8 - mve.to[1] as i8;
-                    if delta.abs() == 2 {
-                        self.en_passant_target_square = Some([mve.from[1] + (delta as usize / 2), mve.to[0]]);
-                    }
+                if piece.piece_type == PieceType::Pawn && (mve.from[1] as i8 - mve.to[1] as i8).abs() == 2 {
+                    self.en_passant_target_square = Some([mve.to[0], (mve.from[1] + mve.to[1]) / 2]);
                 }
             },
-            MoveType::Promote => {
-                // update score
-                score_delta += mve_piece.unwrap().score(mve.to, &self.game_phase);
-
-                // do move
-                self.board[mve.to[1]][mve.to[0]] = mve_piece;
-            },
             MoveType::Castle => {
                 // do move
                 let mve_piece = mve_piece.unwrap();
-                let y = mve.to[1] as usize;
-                if mve_piece.piece_type == PieceType::King {
-                    score_delta += piece.score([6, y], &self.game_phase);
-                    self.board[y][6] = Some(Piece { piece_type: PieceType::King, color: mve_piece.color});
-                    self.board[y][5] = Some(Piece { piece_type: PieceType::Rook, color: mve_piece.color});
-                    if let Some(p) = self.board[y][7] {
-                        score_delta -= p.score([7, y], &self.game_phase);
-                        score_delta += p.score([5, y], &self.game_phase);
-                    }
-                    self.board[y][7] = None;
-                } else if mve_piece.piece_type == PieceType::Queen {
-                    score_delta += piece.score([2, y], &self.game_phase);
-                    self.board[y][2] = Some(Piece { piece_type: PieceType::King, color: mve_piece.color});
-                    self.board[y][3] = Some(Piece { piece_type: PieceType::Rook, color: mve_piece.color});
-                    if let Some(p) = self.board[y][0] {
-                        score_delta -= p.score([0, y], &self.game_phase);
-                        score_delta += p.score([3, y], &self.game_phase);
-                    }
-                    self.board[y][0] = None;
-                }
+                let y = mve.to[1];
+                let (king_x, rook_from_x, rook_to_x) = castle_files(&mve_piece);
 
-                self.disable_castle(&mve_piece.color);
+                score_delta += piece.score([king_x, y], &self.game_phase);
+                if let Some(p) = self.board[y][rook_from_x] {
+                    score_delta -= p.score([rook_from_x, y], &self.game_phase);
+                    score_delta += p.score([rook_to_x, y], &self.game_phase);
+                }
+                self.remove_piece([rook_from_x, y]);
+                self.put_piece([king_x, y], Piece { piece_type: PieceType::King, color: mve_piece.color });
+                self.put_piece([rook_to_x, y], Piece { piece_type: PieceType::Rook, color: mve_piece.color });
             },
             MoveType::EnPassant => {
                 // update score
-                if let Some(p) = self.board[mve.from[1]][mve.to[0]] {
-                    take_piece = Some(p);
-                    take_piece_cord = [mve.to[0], mve.from[1]];
-                }
+                take_piece_cord = [mve.to[0], mve.from[1]];
                 score_delta += piece.score(mve.to, &self.game_phase);
 
                 // do move
-                self.board[mve.from[1]][mve.to[0]] = None;
-                self.board[mve.to[1]][mve.to[0]] = Some(piece);
+                take_piece = self.remove_piece(take_piece_cord);
+                self.put_piece(mve.to, piece);
             },
         }
 
+        // check for disable castle
+        self.update_castle(piece, mve.from);
+        if let Some(p) = take_piece {
+            self.update_castle(p, take_piece_cord);
+        }
+
         if let Some(p) = take_piece {
-            if p.piece_type == PieceType::King {
-                self.score_white = if p.color == Color::White { -i64::MAX } else { i64::MAX };
-                score_delta = 0;
-            } else {
-                score_delta += p.score(take_piece_cord, &self.game_phase);
-            }
+            score_delta += p.score(take_piece_cord, &self.game_phase);
         }
 
         match piece.color {
@@ -140,10 +154,104 @@ impl Game {
             Color::Black => { self.score_white -= score_delta },
         };
 
+        // captures and pawn moves can't be undone, so the positions before them can't be repeated
+        if take_piece.is_some() || piece.piece_type == PieceType::Pawn {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
         if self.on_turn == Color::Black {
             self.fullmove_counter += 1;
         }
-        self.on_turn = if self.on_turn == Color::White { Color::Black } else { Color::White };
+        self.on_turn = self.on_turn.other();
         self.moves.push(*mve);
+        self.hash_history.push(hash);
+
+        self.hash ^= castle_key(&self.castle) ^ self.en_passant_hash() ^ side_key();
+        debug_assert_eq!(self.hash, self.calculate_hash(), "incremental hash differs from calculated hash");
+
+        UndoMove {
+            move_type: mve_type,
+            castle_piece: mve_piece,
+            piece,
+            take_piece,
+            take_piece_cord,
+            castle,
+            en_passant_target_square,
+            score_white,
+            hash,
+            halfmove_clock,
+            fullmove_counter,
+        }
     }
-}
\ No newline at end of file
+
+    pub fn unmake_move(&mut self, mve: &Move, undo: &UndoMove) {
+        //! Will take back the specified Move, which has to be the last move made with ``make_move``.
+
+        match undo.move_type {
+            MoveType::Standard | MoveType::Promote | MoveType::EnPassant => {
+                self.remove_piece(mve.to);
+                if let Some(p) = undo.take_piece {
+                    self.put_piece(undo.take_piece_cord, p);
+                }
+            },
+            MoveType::Castle => {
+                let y = mve.from[1];
+                let (king_x, rook_from_x, rook_to_x) = castle_files(&undo.castle_piece.unwrap());
+
+                self.remove_piece([king_x, y]);
+                self.remove_piece([rook_to_x, y]);
+                self.put_piece([rook_from_x, y], Piece { piece_type: PieceType::Rook, color: undo.piece.color });
+            },
+        }
+        self.put_piece(mve.from, undo.piece);
+
+        self.castle.clear();
+        self.castle.extend(undo.castle.iter().flatten());
+        self.en_passant_target_square = undo.en_passant_target_square;
+        self.score_white = undo.score_white;
+        self.hash = undo.hash;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.fullmove_counter = undo.fullmove_counter;
+        self.on_turn = self.on_turn.other();
+        self.moves.pop();
+        self.hash_history.pop();
+    }
+
+    pub fn make_null_move(&mut self) -> UndoNullMove {
+        //! Pass the turn to the opponent without moving, for the null move pruning of the search.
+        //! 
+        //! The halfmove clock is reset, so no repetitions are found across the null move.
+
+        let undo = UndoNullMove {
+            en_passant_target_square: self.en_passant_target_square,
+            hash: self.hash,
+            halfmove_clock: self.halfmove_clock,
+        };
+
+        self.hash ^= self.en_passant_hash();
+        self.en_passant_target_square = None;
+        self.halfmove_clock = 0;
+        self.on_turn = self.on_turn.other();
+        self.hash ^= side_key();
+        debug_assert_eq!(self.hash, self.calculate_hash(), "incremental hash differs from calculated hash");
+
+        undo
+    }
+
+    pub fn unmake_null_move(&mut self, undo: &UndoNullMove) {
+        self.en_passant_target_square = undo.en_passant_target_square;
+        self.hash = undo.hash;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.on_turn = self.on_turn.other();
+    }
+}
+
+
+fn castle_files(castle_piece: &Piece) -> (usize, usize, usize) {
+    //! Returns the files the king goes to, the rook comes from and the rook goes to for a castle move.
+    //! 
+    //! King = king's side castle, Queen = queen's side castle.
+
+    if castle_piece.piece_type == PieceType::King { (6, 7, 5) } else { (2, 0, 3) }
+}
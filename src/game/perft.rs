@@ -0,0 +1,60 @@
+//! Functions for counting the leaf nodes of the move generation tree ([perft](https://www.chessprogramming.org/Perft)).
+//! 
+//! All functions are in an impl for Game.
+
+use crate::move_struct::{ Move };
+use crate::game::{ Game };
+
+
+impl Game {
+    //! Perft implementations
+
+    #[allow(dead_code)]
+    pub fn perft(&self, depth: u8) -> u64 {
+        //! Returns the number of leaf nodes of the legal move tree of the specified depth.
+
+        self.clone().private_perft(depth)
+    }
+
+    fn private_perft(&mut self, depth: u8) -> u64 {
+        //! Walks the move tree by making and unmaking the moves on this game.
+
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for mve in moves.iter() {
+            let undo = self.make_move(mve);
+            nodes += self.private_perft(depth - 1);
+            self.unmake_move(mve, &undo);
+        }
+
+        nodes
+    }
+
+    pub fn perft_divide(&self, depth: u8) -> Vec<(Move, u64)> {
+        //! Returns the number of leaf nodes for every legal move in the current position.
+        //! 
+        //! The depth includes the root move, so the sum of the counts equals ``perft(depth)``.
+
+        let mut result = Vec::new();
+        if depth == 0 {
+            return result;
+        }
+
+        let mut game = self.clone();
+        for mve in self.legal_moves() {
+            let undo = game.make_move(&mve);
+            result.push((mve, game.private_perft(depth - 1)));
+            game.unmake_move(&mve, &undo);
+        }
+
+        result
+    }
+}
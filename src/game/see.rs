@@ -0,0 +1,85 @@
+//! Static exchange evaluation: what a capture wins or loses when both sides keep taking back on the square.
+//!
+//! All functions are in an impl for Game.
+
+use crate::consts::{ PieceType };
+use crate::move_struct::{ Move };
+use crate::bitboard::{ cord_bitboard };
+use crate::game::{ Game };
+use crate::types::{ Cord };
+
+
+/// Material value per piece type (indexed by ``PieceType as usize``), the king can't be taken so it is worth more than everything else.
+const SEE_VALUES: [i64; 6] = [100, 300, 500, 300, 900, 20000];
+/// The attackers are tried from the least to the most valuable.
+const ATTACKER_ORDER: [PieceType; 6] = [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen, PieceType::King];
+
+
+impl Game {
+    //! Static exchange evaluation implementations
+
+    pub fn see(&self, mve: &Move) -> i64 {
+        //! Returns the material the move wins (or loses when negative) after the exchange on its target square.
+        //!
+        //! Both sides take back with their least valuable attacker and may stop taking when it would lose material.
+        //! Sliding pieces behind the pieces that took (x-ray attackers) join the exchange, pins are ignored.
+
+        let piece = self.board[mve.from[1]][mve.from[0]].unwrap();
+        let promotion_rank = |cord: Cord| cord[1] == 0 || cord[1] == 7;
+        let mut occupied = self.occupied() ^ cord_bitboard(mve.from);
+
+        // gains[depth] is the material won by the side that takes at that depth, if the exchange stops there
+        let mut gains = vec![match self.board[mve.to[1]][mve.to[0]] {
+            Some(victim) => SEE_VALUES[victim.piece_type as usize],
+            // en passant
+            None if piece.piece_type == PieceType::Pawn && mve.from[0] != mve.to[0] => {
+                occupied ^= cord_bitboard([mve.to[0], mve.from[1]]);
+                SEE_VALUES[PieceType::Pawn as usize]
+            },
+            None => 0,
+        }];
+        let mut on_square = SEE_VALUES[piece.piece_type as usize];
+        if let (PieceType::Pawn, Some(promotion)) = (piece.piece_type, mve.piece) {
+            gains[0] += SEE_VALUES[promotion.piece_type as usize] - SEE_VALUES[PieceType::Pawn as usize];
+            on_square = SEE_VALUES[promotion.piece_type as usize];
+        }
+
+        let mut color = piece.color.other();
+        loop {
+            let attackers = self.attackers_to(mve.to, occupied) & occupied;
+            let own_attackers = attackers & self.colors[color as usize];
+            let Some((square, piece_type)) = ATTACKER_ORDER.iter()
+                .map(|piece_type| (own_attackers & self.pieces[*piece_type as usize], *piece_type))
+                .find(|(pieces, _)| *pieces != 0)
+                .map(|(pieces, piece_type)| (pieces.trailing_zeros() as usize, piece_type)) else {
+                break;
+            };
+
+            // the king can't take a defended piece
+            let others = attackers & !self.colors[color as usize];
+            if piece_type == PieceType::King && others != 0 {
+                break;
+            }
+
+            let mut gain = on_square - gains.last().unwrap();
+            on_square = SEE_VALUES[piece_type as usize];
+            if piece_type == PieceType::Pawn && promotion_rank(mve.to) {
+                gain += SEE_VALUES[PieceType::Queen as usize] - SEE_VALUES[PieceType::Pawn as usize];
+                on_square = SEE_VALUES[PieceType::Queen as usize];
+            }
+            gains.push(gain);
+
+            occupied ^= 1 << square;
+            color = color.other();
+        }
+
+        // going back, each side only takes if it doesn't lose by it
+        while gains.len() > 1 {
+            let last = gains.pop().unwrap();
+            let previous = gains.last_mut().unwrap();
+            *previous = -(-*previous).max(last);
+        }
+
+        gains[0]
+    }
+}
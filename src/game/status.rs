@@ -0,0 +1,35 @@
+//! Functions to find out whether a game is over.
+//! 
+//! All functions are in an impl for Game.
+
+use crate::consts::{ GameStatus };
+use crate::game::{ Game };
+
+
+impl Game {
+    //! Status implementations
+
+    #[allow(dead_code)]
+    pub fn status(&self) -> GameStatus {
+        //! Returns whether the game is over, and why.
+        //! 
+        //! A mate on the last move counts, even if the same move also completes fifty or seventy-five moves.
+        //! The fifty-move rule and threefold repetition are draws a player has to claim, they are reported as if the claim is made.
+
+        if self.legal_moves().is_empty() {
+            return if self.is_check() { GameStatus::Checkmate(self.on_turn.other()) } else { GameStatus::Stalemate };
+        }
+
+        if self.is_insufficient_material() {
+            GameStatus::InsufficientMaterial
+        } else if self.is_seventy_five_move_draw() {
+            GameStatus::SeventyFiveMove
+        } else if self.is_fifty_move_draw() {
+            GameStatus::FiftyMoveRule
+        } else if self.is_threefold_repetition() {
+            GameStatus::ThreefoldRepetition
+        } else {
+            GameStatus::Ongoing
+        }
+    }
+}
@@ -3,80 +3,175 @@
 //! All functions are in an impl for Game.
 
 use crate::piece::{ Piece };
-use crate::consts::{ Color, GamePhase };
-use crate::utils::{ string_square_to_square };
+use crate::consts::{ Color, PieceType, GamePhase };
+use crate::utils::{ parse_square, square_to_string };
 use crate::game::{ Game };
+use std::fmt;
+
+
+/// The castling rights in FEN order, with the squares the king and the rook have to be on.
+const CASTLING_RIGHTS: [(char, [usize; 2], [usize; 2]); 4] = [
+    ('K', [4, 0], [7, 0]),
+    ('Q', [4, 0], [0, 0]),
+    ('k', [4, 7], [7, 7]),
+    ('q', [4, 7], [0, 7]),
+];
+
+
+/// Reasons a FEN string can't be loaded.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FenError {
+    /// Number of space separated fields, there have to be 4 (without the clocks) to 6.
+    FieldCount(usize),
+    /// Number of ranks of the board, there have to be 8.
+    RankCount(usize),
+    /// A rank (1 to 8) that doesn't have 8 squares.
+    RankLength { rank: usize, length: usize },
+    UnknownPiece(char),
+    MissingKing(Color),
+    TooManyKings(Color),
+    SideToMove(String),
+    /// Unknown or repeated castling rights, or rights for a king or rook that isn't on its starting square.
+    CastlingRights(String),
+    /// A square that isn't right behind a pawn that just moved two squares.
+    EnPassantSquare(String),
+    Clock(String),
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FenError::FieldCount(count) => write!(formatter, "expected 4 to 6 fields, found {}", count),
+            FenError::RankCount(count) => write!(formatter, "expected 8 ranks, found {}", count),
+            FenError::RankLength { rank, length } => write!(formatter, "rank {} has {} squares instead of 8", rank, length),
+            FenError::UnknownPiece(chr) => write!(formatter, "unknown piece `{}`", chr),
+            FenError::MissingKing(color) => write!(formatter, "{:?} has no king", color),
+            FenError::TooManyKings(color) => write!(formatter, "{:?} has more than one king", color),
+            FenError::SideToMove(value) => write!(formatter, "invalid side to move `{}`", value),
+            FenError::CastlingRights(value) => write!(formatter, "invalid castling rights `{}`", value),
+            FenError::EnPassantSquare(value) => write!(formatter, "impossible en passant square `{}`", value),
+            FenError::Clock(value) => write!(formatter, "invalid move number `{}`", value),
+        }
+    }
+}
 
 
 impl Game {
     //! Storage implementations
 
-    pub fn from_fen(fen_code: String) -> Game {
+    pub fn from_fen(fen_code: String) -> Result<Game, FenError> {
         //! Load a game from a [FEN string](https://www.chessprogramming.org/Forsyth-Edwards_Notation).
         //! 
+        //! The halfmove clock and the fullmove number may be left out, they default to ``0 1``.
+        //! 
         //! ```
-        //! Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"));
+        //! let game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
         //! ```
 
-        let splitted_fen = fen_code.split_whitespace().take(6).collect::<Vec<&str>>();
+        let fields = fen_code.split_whitespace().collect::<Vec<&str>>();
+        if !(4..=6).contains(&fields.len()) {
+            return Err(FenError::FieldCount(fields.len()));
+        }
+
+        // the ranks are listed from rank 8 down to rank 1
+        let ranks = fields[0].split('/').collect::<Vec<&str>>();
+        if ranks.len() != 8 {
+            return Err(FenError::RankCount(ranks.len()));
+        }
         let mut board: [[Option<Piece>; 8]; 8] = Default::default();
-        let mut castle_vec = Vec::new();
-        let on_turn;
-        let en_passant_target_square;
-        let fullmove_counter;
-
-        if let [board_string, on_turn_fen_let, castling, en_passant_target_square_string, _halfmove_clock, fullmove_counter_string] = &splitted_fen[..] {
-            for (y, rank) in board_string.rsplit('/').enumerate() {
-                let mut x = 0;
-                for chr in rank.chars() {
-                    match chr.to_digit(10) {
-                        None => {
-                            board[y][x] = Some(Piece::from_fen(chr));
-                            x += 1;
-                        },
-                        Some(num) => {
-                            for x_delta in 0usize..(num as usize) {
-                                board[y][x + x_delta] = None;
-                            }
-                            x += num as usize;
-                        },
-                    }
+        for (y, rank) in ranks.iter().rev().enumerate() {
+            let mut squares = Vec::new();
+            for chr in rank.chars() {
+                match chr.to_digit(10) {
+                    Some(num @ 1..=8) => squares.extend((0..num).map(|_| None)),
+                    _ if "KQRBNPkqrbnp".contains(chr) => squares.push(Some(Piece::from_fen(chr))),
+                    _ => return Err(FenError::UnknownPiece(chr)),
                 }
             }
-            
-            on_turn = if *on_turn_fen_let == "w" { Color::White } else { Color::Black };
+            if squares.len() != 8 {
+                return Err(FenError::RankLength { rank: y + 1, length: squares.len() });
+            }
+            board[y].copy_from_slice(&squares);
+        }
 
-            for piece_char in castling.chars() {
-                if piece_char == '-' { break; }
-                castle_vec.push(Piece::from_fen(piece_char));
+        for color in [Color::White, Color::Black] {
+            let king = Some(Piece { piece_type: PieceType::King, color });
+            match board.iter().flatten().filter(|piece| **piece == king).count() {
+                0 => return Err(FenError::MissingKing(color)),
+                1 => {},
+                _ => return Err(FenError::TooManyKings(color)),
             }
+        }
 
-            if *en_passant_target_square_string != "-" {
-                en_passant_target_square = Some(string_square_to_square(en_passant_target_square_string.to_string()));
-            } else {
-                en_passant_target_square = None;
+        let on_turn = match fields[1] {
+            "w" => Color::White,
+            "b" => Color::Black,
+            value => return Err(FenError::SideToMove(value.to_string())),
+        };
+
+        // castling rights are stored in FEN order, whatever the order of the string
+        let castling = fields[2];
+        let mut castle = Vec::new();
+        if castling != "-" {
+            let all_known = castling.chars().all(|chr| CASTLING_RIGHTS.iter().any(|right| right.0 == chr));
+            let no_repeats = CASTLING_RIGHTS.iter().all(|right| castling.matches(right.0).count() <= 1);
+            if castling.is_empty() || !all_known || !no_repeats {
+                return Err(FenError::CastlingRights(castling.to_string()));
             }
 
-            fullmove_counter = fullmove_counter_string.parse::<usize>().unwrap();
-        } else {
-            panic!("Illegal FEN code");
+            for (chr, king_cord, rook_cord) in CASTLING_RIGHTS.iter().filter(|right| castling.contains(right.0)) {
+                let piece = Piece::from_fen(*chr);
+                let king = Some(Piece { piece_type: PieceType::King, color: piece.color });
+                let rook = Some(Piece { piece_type: PieceType::Rook, color: piece.color });
+                if board[king_cord[1]][king_cord[0]] != king || board[rook_cord[1]][rook_cord[0]] != rook {
+                    return Err(FenError::CastlingRights(castling.to_string()));
+                }
+                castle.push(piece);
+            }
         }
 
+        // the pawn that moved two squares stands in front of the square, the square and the one it came from are empty
+        let en_passant_target_square = match fields[3] {
+            "-" => None,
+            value => {
+                let (y, pawn_y, from_y) = if on_turn == Color::White { (5, 4, 6) } else { (2, 3, 1) };
+                let pawn = Some(Piece { piece_type: PieceType::Pawn, color: on_turn.other() });
+                match parse_square(value) {
+                    Some([x, square_y]) if square_y == y && board[pawn_y][x] == pawn && board[y][x].is_none() && board[from_y][x].is_none() => Some([x, y]),
+                    _ => return Err(FenError::EnPassantSquare(value.to_string())),
+                }
+            },
+        };
+
+        let clock = |idx: usize, default: usize| match fields.get(idx) {
+            Some(value) => value.parse::<usize>().map_err(|_| FenError::Clock(value.to_string())),
+            None => Ok(default),
+        };
+        let halfmove_clock = clock(4, 0)?;
+        let fullmove_counter = clock(5, 1)?;
+
         let mut new_game = Game {
             board,
+            pieces: [0; 6],
+            colors: [0; 2],
             on_turn,
-            castle: castle_vec,
+            castle,
             en_passant_target_square,
             score_white: 0,
+            hash: 0,
+            hash_history: Vec::new(),
             moves: Vec::new(),
+            halfmove_clock,
             fullmove_counter,
             game_phase: GamePhase::Start,
         };
 
-        new_game.calculate_board_score();
+        new_game.calculate_bitboards();
         new_game.calculate_game_phase();
+        new_game.calculate_board_score();
+        new_game.hash = new_game.calculate_hash();
 
-        new_game
+        Ok(new_game)
     }
 
     #[allow(dead_code)]
@@ -85,7 +180,7 @@ impl Game {
         //! 
         //! ```
         //! let start_fen_string = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
-        //! let game = Game::from_fen(start_fen_string.to_string());
+        //! let game = Game::from_fen(start_fen_string.to_string()).unwrap();
         //! 
         //! assert_eq!(game.to_fen(), start_fen_string);
         //! ```
@@ -121,14 +216,17 @@ impl Game {
         };
 
         let mut castling_string = String::new();
-        if !self.castle.is_empty() {
-            for piece in self.castle.iter() {
-                castling_string += &piece.to_fen();
+        for (chr, _, _) in CASTLING_RIGHTS.iter() {
+            if self.castle.contains(&Piece::from_fen(*chr)) {
+                castling_string.push(*chr);
             }
-        } else {
+        }
+        if castling_string.is_empty() {
             castling_string = String::from("-");
         }
 
-        format!("{} {} {} - 0 1", board_string, on_turn, castling_string)
+        let en_passant = self.en_passant_target_square.map_or(String::from("-"), square_to_string);
+
+        format!("{} {} {} {} {} {}", board_string, on_turn, castling_string, en_passant, self.halfmove_clock, self.fullmove_counter)
     }
 }
@@ -22,10 +22,10 @@ impl Logger {
     pub fn new(file_name: &str) -> Logger {
         //! Create new logger.
         //! 
-        //! Output file will have the specified name.
+        //! Output file will have the specified name. With an empty name nothing is logged.
 
         let new_path = Path::new(file_name);
-        if !new_path.exists() {
+        if !file_name.is_empty() && !new_path.exists() {
             File::create(new_path).unwrap();
         }
         Logger { file_name: String::from(file_name) }
@@ -34,10 +34,14 @@ impl Logger {
     pub fn log(&self, log_type: LogType, text: String) {
         //! Logs the specified text with a log type of Info Warn or Err.
 
+        if self.file_name.is_empty() {
+            return;
+        }
+
         let now = Utc::now().format("%Y-%m-%dT%T");
         let log_string = format!("{} [{}] {}\n", now, format!("{:?}", log_type).to_lowercase(), text);
 
-        let mut file = fs::OpenOptions::new().write(true).append(true).open(self.file_name.to_string()).unwrap();
+        let mut file = fs::OpenOptions::new().append(true).open(&self.file_name).unwrap();
         write!(file, "{}", log_string).unwrap();
     }
 }
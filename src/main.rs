@@ -6,6 +6,8 @@
 use uci::{ uci };
 use argparse::{ArgumentParser, Store, StoreOption };
 use benchmark::{ run_benchmarks };
+use perft::{ run_perft };
+use epd::{ run_epd };
 
 mod consts;
 mod game;
@@ -18,12 +20,26 @@ mod openings;
 mod types;
 mod uci;
 mod benchmark;
+mod bitboard;
+mod zobrist;
+mod transposition_table;
+mod time_manager;
+mod search;
+mod move_ordering;
+mod uci_command;
+mod perft;
+mod pgn;
+mod epd;
 #[cfg(test)]
 mod tests;
 
 fn main() {
     let mut mode = String::new();
     let mut save_file = None;
+    let mut depth: u8 = 5;
+    let mut fen_code = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    let mut epd_file = String::new();
+    let mut movetime = None;
 
     {  // this block limits scope of borrows by ap.refer() method
         let mut ap = ArgumentParser::new();
@@ -32,18 +48,34 @@ fn main() {
 
         ap.refer(&mut mode)
             .add_argument("MODE", Store,
-            "Mode to run chess AI in. Choose from: [UCI, benchmark]");
+            "Mode to run chess AI in. Choose from: [UCI, benchmark, perft, epd]");
         ap.refer(&mut save_file)
             .add_option(&["-s", "--save"], StoreOption,
             "File to save output to");
+        ap.refer(&mut depth)
+            .add_option(&["-d", "--depth"], Store,
+            "Depth to run perft or to search the EPD positions to");
+        ap.refer(&mut fen_code)
+            .add_option(&["-f", "--fen"], Store,
+            "FEN code of the position to run perft on");
+        ap.refer(&mut epd_file)
+            .add_option(&["-e", "--epd"], Store,
+            "EPD file with the positions to search");
+        ap.refer(&mut movetime)
+            .add_option(&["-t", "--movetime"], StoreOption,
+            "Milliseconds to search each EPD position, instead of searching to a depth");
         ap.parse_args_or_exit();
 
     }
 
     if mode.to_uppercase() == *"UCI".to_string() {
         uci();
-    } else if vec!["bench".to_string(), "benchmark".to_string()].contains(&mode.to_lowercase()) {
+    } else if ["bench".to_string(), "benchmark".to_string()].contains(&mode.to_lowercase()) {
         run_benchmarks(save_file);
+    } else if mode.to_lowercase() == "perft" {
+        run_perft(fen_code, depth);
+    } else if mode.to_lowercase() == "epd" {
+        run_epd(epd_file, depth, movetime);
     } else {
         panic!("'{}' is not a valid mode.", mode);
     }
@@ -0,0 +1,129 @@
+//! Move ordering for the search.
+//!
+//! Alpha-beta prunes the most when the best move is searched first, so the moves are sorted by how likely they are to be good:
+//! the hash move, captures and promotions that don't lose material (most valuable victim first), killer moves, the countermove,
+//! the other quiet moves by their history and last the captures that lose material.
+
+use crate::game::{ Game };
+use crate::consts::{ Color, MAX_PLY };
+use crate::move_struct::{ Move };
+use crate::bitboard::{ square_index };
+use std::cmp::{ Reverse };
+
+
+const HASH_MOVE_SCORE: i64 = 1 << 50;
+const CAPTURE_SCORE: i64 = 1 << 40;
+const KILLER_SCORE: i64 = 1 << 30;
+const COUNTERMOVE_SCORE: i64 = 1 << 29;
+const LOSING_CAPTURE_SCORE: i64 = -(1 << 40);
+/// History scores stay between minus and plus this, so they stay below the countermove and above the losing captures.
+const MAX_HISTORY: i64 = 1 << 20;
+
+
+/// The statistics of the search that are used to order the quiet moves, every search thread has its own.
+pub struct MoveOrdering {
+    /// Two quiet moves per ply that caused a beta cutoff in a sibling position.
+    killers: Vec<[Option<Move>; 2]>,
+    /// How well a quiet move did, per color, from square and to square.
+    history: Vec<i64>,
+    /// The quiet move that refuted a move, per from square and to square of that move.
+    countermoves: Vec<Option<Move>>,
+}
+
+impl MoveOrdering {
+    pub fn new() -> MoveOrdering {
+        MoveOrdering {
+            killers: vec![[None; 2]; MAX_PLY + 1],
+            history: vec![0; 2 * 64 * 64],
+            countermoves: vec![None; 64 * 64],
+        }
+    }
+
+    pub fn sort_moves(&self, game: &Game, moves: &mut [Move], hash_move: Option<Move>, ply: usize) -> Vec<i64> {
+        //! Sort the moves of the game at the specified ply from most to least promising.
+        //!
+        //! Returns the ordering score of every move in the sorted order, see ``is_losing_capture``.
+
+        let countermove = game.moves.last().and_then(|previous| self.countermoves[move_index(previous)]);
+        let mut scored: Vec<(i64, Move)> = moves.iter().map(|mve| {
+            let score = if Some(*mve) == hash_move {
+                HASH_MOVE_SCORE
+            } else if game.is_capture_or_promotion(mve) {
+                let see = game.see(mve);
+                if see >= 0 {
+                    // most valuable victim first, taken by the least valuable attacker
+                    let attacker = game.board[mve.from[1]][mve.from[0]].unwrap();
+                    CAPTURE_SCORE + game.capture_gain(mve) * 1024 - attacker.score(mve.from, &game.game_phase)
+                } else {
+                    LOSING_CAPTURE_SCORE + see
+                }
+            } else if self.killers[ply][0] == Some(*mve) {
+                KILLER_SCORE + 1
+            } else if self.killers[ply][1] == Some(*mve) {
+                KILLER_SCORE
+            } else if countermove == Some(*mve) {
+                COUNTERMOVE_SCORE
+            } else {
+                self.history[history_index(game.on_turn, mve)]
+            };
+
+            (score, *mve)
+        }).collect();
+        scored.sort_by_key(|(score, _)| Reverse(*score));
+
+        moves.iter_mut().zip(scored.iter()).for_each(|(mve, (_, sorted))| *mve = *sorted);
+        scored.into_iter().map(|(score, _)| score).collect()
+    }
+
+    pub fn update(&mut self, game: &Game, mve: &Move, ply: usize, depth: u8, tried_quiets: &[Move]) {
+        //! The quiet move caused a beta cutoff at the specified ply, after the other quiet moves didn't.
+        //!
+        //! It becomes a killer move and the countermove of the previous move, and its history goes up while the history of the others goes down.
+
+        if self.killers[ply][0] != Some(*mve) {
+            self.killers[ply][1] = self.killers[ply][0];
+            self.killers[ply][0] = Some(*mve);
+        }
+        if let Some(previous) = game.moves.last() {
+            self.countermoves[move_index(previous)] = Some(*mve);
+        }
+
+        let bonus = (depth as i64 * depth as i64).min(MAX_HISTORY);
+        for quiet in tried_quiets {
+            add_history(&mut self.history[history_index(game.on_turn, quiet)], -bonus);
+        }
+        add_history(&mut self.history[history_index(game.on_turn, mve)], bonus);
+    }
+
+    #[allow(dead_code)]
+    pub fn killers(&self, ply: usize) -> [Option<Move>; 2] {
+        self.killers[ply]
+    }
+
+    #[allow(dead_code)]
+    pub fn history(&self, color: Color, mve: &Move) -> i64 {
+        self.history[history_index(color, mve)]
+    }
+}
+
+
+pub fn is_losing_capture(score: i64) -> bool {
+    //! Returns true if the ordering score is of a capture or promotion that loses material in the static exchange evaluation.
+
+    score < -MAX_HISTORY
+}
+
+fn move_index(mve: &Move) -> usize {
+    square_index(mve.from) * 64 + square_index(mve.to)
+}
+
+fn history_index(color: Color, mve: &Move) -> usize {
+    color as usize * 64 * 64 + move_index(mve)
+}
+
+fn add_history(score: &mut i64, bonus: i64) {
+    //! Add the bonus (or penalty when negative) to a history score, the closer the score already is to ``MAX_HISTORY``
+    //! in the same direction the less it changes, so it never gets past it.
+
+    *score += bonus - *score * bonus.abs() / MAX_HISTORY;
+}
@@ -5,10 +5,13 @@
 use crate::consts::{ MoveType, PieceType, Color };
 use crate::types::{ Cord };
 use crate::piece::{ Piece };
+use crate::bitboard::{ square_index, index_to_cord };
+use crate::utils::{ parse_square, square_to_string };
+use crate::game::{ Game };
 use std::fmt;
 
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq)]
 pub struct Move {
     pub from: Cord,
     pub to: Cord,
@@ -25,6 +28,16 @@ impl Move {
         }
     }
 
+    pub fn is_long_algebraic_notation(notation: &str) -> bool {
+        //! True if the notation is a valid move in long algebraic notation (it may still be illegal).
+
+        let chars = notation.chars().collect::<Vec<char>>();
+        (chars.len() == 4 || chars.len() == 5)
+            && ('a'..='h').contains(&chars[0]) && ('1'..='8').contains(&chars[1])
+            && ('a'..='h').contains(&chars[2]) && ('1'..='8').contains(&chars[3])
+            && chars.get(4).is_none_or(|piece| "nbrq".contains(*piece))
+    }
+
     pub fn from_long_algebraic_notation(notation: String) -> Move {
         //! Load a move from the long algebraic notation
         //! 
@@ -37,19 +50,23 @@ impl Move {
         let to_str = (notation[2], notation[3].to_digit(10).unwrap() - 1);
 
         let from: Cord = [
-            vec!['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'].iter().position(|&x| x == from_str.0).unwrap(),
+            ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'].iter().position(|&x| x == from_str.0).unwrap(),
             from_str.1  as usize,
         ];
 
         let to: Cord  = [
-            vec!['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'].iter().position(|&x| x == to_str.0).unwrap(),
+            ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'].iter().position(|&x| x == to_str.0).unwrap(),
             to_str.1 as usize,
         ];
 
 
+        // the color of a promoted piece follows from the rank the pawn moves to
         let mut piece = None;
         if notation.len() > 4 {
-            piece = Some(Piece::from_fen(notation[4]));
+            piece = Some(Piece {
+                piece_type: Piece::from_fen(notation[4]).piece_type,
+                color: if to[1] == 7 { Color::White } else { Color::Black },
+            });
         }
 
 
@@ -60,6 +77,121 @@ impl Move {
         }
     }
 
+    pub fn to_san(self, game: &Game) -> String {
+        //! Dump the move to [standard algebraic notation](https://en.wikipedia.org/wiki/Algebraic_notation_(chess)),
+        //! the move has to be legal in the game.
+        //! 
+        //! Examples of standard algebraic notation:
+        //! e4 (pawn to e4)  
+        //! Nbxd2+ (the knight on the b file takes on d2 and gives check)  
+        //! exd8=Q# (pawn takes on d8, promotes to a queen and mates)  
+        //! O-O-O (queen's side castle)
+
+        let piece = game.board[self.from[1]][self.from[0]].unwrap();
+        let mut san = if piece.piece_type == PieceType::King && self.from[0].abs_diff(self.to[0]) == 2 {
+            String::from(if self.to[0] == 6 { "O-O" } else { "O-O-O" })
+        } else {
+            let is_capture = game.board[self.to[1]][self.to[0]].is_some() || (piece.piece_type == PieceType::Pawn && self.from[0] != self.to[0]);
+            let mut san = String::new();
+            if piece.piece_type == PieceType::Pawn {
+                if is_capture {
+                    san.push(square_to_string(self.from).remove(0));
+                }
+            } else {
+                san += &piece.to_fen().to_uppercase();
+
+                // name the file, the rank or both of the piece if another piece of the same type can go to the same square
+                let others = game.legal_moves().into_iter()
+                    .filter(|mve| mve.to == self.to && mve.from != self.from && game.board[mve.from[1]][mve.from[0]] == Some(piece))
+                    .collect::<Vec<Move>>();
+                let from = square_to_string(self.from);
+                if !others.is_empty() {
+                    if others.iter().all(|mve| mve.from[0] != self.from[0]) {
+                        san += &from[..1];
+                    } else if others.iter().all(|mve| mve.from[1] != self.from[1]) {
+                        san += &from[1..];
+                    } else {
+                        san += &from;
+                    }
+                }
+            }
+
+            if is_capture {
+                san.push('x');
+            }
+            san += &square_to_string(self.to);
+            if let (PieceType::Pawn, Some(promotion)) = (piece.piece_type, self.piece) {
+                san += &format!("={}", promotion.to_fen().to_uppercase());
+            }
+            san
+        };
+
+        let mut new_game = game.clone();
+        new_game.do_move(&self);
+        if new_game.is_check() {
+            san.push(if new_game.legal_moves().is_empty() { '#' } else { '+' });
+        }
+
+        san
+    }
+
+    pub fn from_san(notation: &str, game: &Game) -> Option<Move> {
+        //! Load a move from [standard algebraic notation](https://en.wikipedia.org/wiki/Algebraic_notation_(chess)).
+        //! 
+        //! Returns None if the notation isn't a legal move in the game, or if it could be more than one legal move.
+        //! Check marks and annotations are ignored, and the common variations humans write are accepted:
+        //! ``0-0`` for castling, a piece named that didn't need to be, or a promotion without the ``=``.
+
+        let notation = notation.trim().trim_end_matches(['+', '#', '!', '?']).replace('0', "O");
+        let legal_moves = game.legal_moves();
+        let piece_type_at = |cord: Cord| game.board[cord[1]][cord[0]].map(|piece| piece.piece_type);
+        // castling has to be written as O-O or O-O-O, not as the king move
+        let is_castle = |mve: &Move| piece_type_at(mve.from) == Some(PieceType::King) && mve.from[0].abs_diff(mve.to[0]) == 2;
+
+        if notation == "O-O" || notation == "O-O-O" {
+            let file = if notation == "O-O" { 6 } else { 2 };
+            return legal_moves.into_iter().find(|mve| is_castle(mve) && mve.to[0] == file);
+        }
+
+        let mut chars = notation.chars().filter(|chr| !"x:-=".contains(*chr)).collect::<Vec<char>>();
+        let piece_type = match chars.first() {
+            Some(chr) if "NBRQK".contains(*chr) => Piece::from_fen(chars.remove(0)).piece_type,
+            _ => PieceType::Pawn,
+        };
+        let promotion = match chars.last() {
+            Some(chr) if "NBRQ".contains(*chr) && piece_type == PieceType::Pawn => Some(Piece::from_fen(chars.pop().unwrap()).piece_type),
+            _ => None,
+        };
+        if chars.len() < 2 {
+            return None;
+        }
+        let to = parse_square(&chars.split_off(chars.len() - 2).into_iter().collect::<String>())?;
+
+        // what is left names the file and/or the rank the piece comes from
+        let mut from_file = None;
+        let mut from_rank = None;
+        for chr in chars {
+            match chr {
+                'a'..='h' if from_file.is_none() && from_rank.is_none() => from_file = Some(chr as usize - 'a' as usize),
+                '1'..='8' if from_rank.is_none() => from_rank = Some(chr as usize - '1' as usize),
+                _ => return None,
+            }
+        }
+
+        let mut candidates = legal_moves.into_iter().filter(|mve| {
+            mve.to == to
+                && piece_type_at(mve.from) == Some(piece_type)
+                && !is_castle(mve)
+                && from_file.is_none_or(|file| mve.from[0] == file)
+                && from_rank.is_none_or(|rank| mve.from[1] == rank)
+                && (piece_type != PieceType::Pawn || mve.piece.map(|piece| piece.piece_type) == promotion)
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some(mve), None) => Some(mve),
+            _ => None,
+        }
+    }
+
     #[allow(dead_code)]
     pub fn repr(&self) -> String {
         //! Simple ray to represent move in console.
@@ -77,7 +209,8 @@ impl Move {
         };
 
         if let Some(castling) = castling {
-            if self.from[0] == 4 && (self.from[1] == 0 || self.from[1] == 7) {
+            let can_be_king = piece_type.is_none() || piece_type == Some(PieceType::King);
+            if can_be_king && self.from[0] == 4 && (self.from[1] == 0 || self.from[1] == 7) {
                 let color = if self.from[1] == 0 { Color::White } else { Color::Black };
                 let king = Piece { piece_type: PieceType::King, color};
                 let queen = Piece { piece_type: PieceType::Queen, color};
@@ -127,6 +260,48 @@ impl Move {
 
         format!("{}{}{}", from, to, promotion)
     }
+
+    pub fn to_u16(self) -> u16 {
+        //! Pack the move in 16 bits: 6 bits from square, 6 bits to square and 4 bits for the piece.
+        //! 
+        //! Used to store moves compactly (in the transposition table). Unpack with ``Move::from_u16``.
+
+        let flag = match self.piece {
+            None => 0,
+            // castle moves are made from the first or last rank, promotions never
+            Some(p) if self.from[1] == 0 || self.from[1] == 7 => if p.piece_type == PieceType::King { 5 } else { 6 },
+            Some(p) => match p.piece_type {
+                PieceType::Knight => 1,
+                PieceType::Bishop => 2,
+                PieceType::Rook => 3,
+                _ => 4,
+            },
+        };
+
+        (square_index(self.from) | square_index(self.to) << 6 | flag << 12) as u16
+    }
+
+    pub fn from_u16(packed: u16) -> Move {
+        //! Unpack a move packed by ``Move::to_u16``.
+
+        let from = index_to_cord((packed & 0x3f) as usize);
+        let to = index_to_cord((packed >> 6 & 0x3f) as usize);
+        let piece_type = match packed >> 12 {
+            1 => Some(PieceType::Knight),
+            2 => Some(PieceType::Bishop),
+            3 => Some(PieceType::Rook),
+            4 | 6 => Some(PieceType::Queen),
+            5 => Some(PieceType::King),
+            _ => None,
+        };
+        let color = if (packed >> 12 >= 5 && from[1] == 0) || (packed >> 12 < 5 && to[1] == 7) { Color::White } else { Color::Black };
+
+        Move {
+            from,
+            to,
+            piece: piece_type.map(|piece_type| Piece { piece_type, color }),
+        }
+    }
 }
 
 impl fmt::Debug for Move {
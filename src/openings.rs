@@ -8,6 +8,10 @@ use std::fs;
 use std::path::{ Path };
 use rand::Rng;
 
+
+/// The openings database that comes with the engine.
+pub const DEFAULT_BOOK_FILE: &str = "./chess_openings.txt";
+
 #[derive(Clone)]
 struct Opening {
     // eco: String,
@@ -29,10 +33,18 @@ impl OpeningsDatabase {
         //! 
         //! Automatically opens the database file. Or panics it if it doesn't exist.
 
-        let path = Path::new("./chess_openings.txt");
+        let path = Path::new(DEFAULT_BOOK_FILE);
         if !path.exists() { panic!("No openings database found!"); }
 
-        let contents = fs::read_to_string(path).expect("Failed to read file");
+        OpeningsDatabase::from_file(DEFAULT_BOOK_FILE).expect("Failed to read file")
+    }
+
+    pub fn from_file(file_name: &str) -> Result<OpeningsDatabase, String> {
+        //! Open the OpeningsDatabase in the specified file.
+        //! 
+        //! Returns an error if the file can't be read.
+
+        let contents = fs::read_to_string(file_name).map_err(|error| format!("Can't read `{}`: {}", file_name, error))?;
         let mut openings = Vec::new();
         let mut eco = None;
         let mut name = None;
@@ -52,6 +64,9 @@ impl OpeningsDatabase {
                 let mut new_moves = Vec::new();
                 for (idx, item) in splitted_line.iter().enumerate() {
                     if idx == 0 { continue }
+                    if !Move::is_long_algebraic_notation(item) {
+                        return Err(format!("Invalid move `{}` in `{}`", item, file_name));
+                    }
                     new_moves.push(Move::from_long_algebraic_notation(item.to_string()));
                 }
                 moves = Some(new_moves)
@@ -69,9 +84,9 @@ impl OpeningsDatabase {
             }
         }
 
-        OpeningsDatabase {
+        Ok(OpeningsDatabase {
             openings
-        }
+        })
     }
 
     pub fn find_opening(&self, moves: &[Move]) -> Option<Move> {
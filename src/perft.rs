@@ -0,0 +1,28 @@
+//! Perft mode, for validating the move generator against known node counts.
+
+use crate::game::{ Game };
+use std::time::{ SystemTime };
+
+
+pub fn run_perft(fen_code: String, depth: u8) {
+    //! Prints the number of leaf nodes per root move (divide output) and the total for the specified position and depth.
+
+    let game = match Game::from_fen(fen_code) {
+        Ok(game) => game,
+        Err(error) => {
+            println!("Invalid FEN: {}", error);
+            return;
+        },
+    };
+    let start = SystemTime::now();
+
+    let mut total = 0;
+    for (mve, nodes) in game.perft_divide(depth).iter() {
+        println!("{}: {}", mve.long_algebraic_notation(), nodes);
+        total += nodes;
+    }
+
+    let elapsed = start.elapsed().unwrap().as_millis();
+    println!("\nNodes searched: {}", total);
+    println!("Time: {}ms", elapsed);
+}
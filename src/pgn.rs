@@ -0,0 +1,346 @@
+//! Reading and writing games in [PGN](https://en.wikipedia.org/wiki/Portable_Game_Notation).
+//!
+//! Only the main line of a game is kept: comments, NAGs and variations are read, but skipped.
+
+use crate::consts::{ Color };
+use crate::game::{ Game };
+use crate::game::storage::{ FenError };
+use crate::move_struct::{ Move };
+use crate::uci_command::{ START_FEN };
+use std::fmt;
+use std::fs;
+
+
+/// The tags every game has, written in this order before any other tag.
+const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+/// The results that end the movetext of a game.
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+/// Maximum length of a line of movetext.
+const MAX_LINE_LENGTH: usize = 80;
+
+
+/// A single game of a PGN file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PgnGame {
+    /// Tag pairs in the order they were read or set.
+    pub tags: Vec<(String, String)>,
+    /// The main line, from the position of the ``FEN`` tag or else the normal starting position.
+    pub moves: Vec<Move>,
+    /// ``1-0``, ``0-1``, ``1/2-1/2`` or ``*`` for an unfinished game.
+    pub result: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PgnError {
+    /// A tag pair that isn't ``[Name "value"]``.
+    InvalidTag(String),
+    InvalidFen(FenError),
+    /// A move that isn't legal (or ambiguous), in the game with the specified number counting from 1 when reading several games.
+    IllegalMove { game: Option<usize>, notation: String },
+    UnterminatedComment,
+    UnbalancedVariation,
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PgnError::InvalidTag(tag) => write!(formatter, "invalid tag `{}`", tag),
+            PgnError::InvalidFen(error) => write!(formatter, "invalid FEN tag: {}", error),
+            PgnError::IllegalMove { game: Some(game), notation } => write!(formatter, "illegal move `{}` in game {}", notation, game),
+            PgnError::IllegalMove { game: None, notation } => write!(formatter, "illegal move `{}`", notation),
+            PgnError::UnterminatedComment => write!(formatter, "comment without a closing `}}`"),
+            PgnError::UnbalancedVariation => write!(formatter, "unbalanced parentheses around a variation"),
+        }
+    }
+}
+
+
+/// The pieces the text of a PGN file consists of.
+enum Token {
+    Tag(String, String),
+    /// Move number, move or result.
+    Symbol(String),
+    VariationStart,
+    VariationEnd,
+    /// Comments and numeric annotation glyphs, which are skipped.
+    Annotation,
+}
+
+
+impl PgnGame {
+    pub fn new(start: &Game, moves: &[Move]) -> PgnGame {
+        //! A game of the moves (which have to be legal) played from the start position.
+        //!
+        //! The result follows from the final position, a game that isn't over gets ``*``.
+
+        let mut game = start.clone();
+        game.apply_moves(moves);
+
+        let mut pgn_game = PgnGame { tags: Vec::new(), moves: moves.to_vec(), result: String::from(game.status().result()) };
+        let fen = start.to_fen();
+        if fen != START_FEN {
+            pgn_game.set_tag("SetUp", "1");
+            pgn_game.set_tag("FEN", &fen);
+        }
+
+        pgn_game
+    }
+
+    #[allow(dead_code)]
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        //! Returns the value of the tag with the specified name.
+
+        self.tags.iter().find(|(tag_name, _)| tag_name == name).map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        //! Set the value of a tag, adding the tag if the game doesn't have it yet.
+
+        match self.tags.iter_mut().find(|(tag_name, _)| tag_name == name) {
+            Some(tag) => tag.1 = String::from(value),
+            None => self.tags.push((String::from(name), String::from(value))),
+        }
+    }
+
+    pub fn start_position(&self) -> Result<Game, PgnError> {
+        //! The position the moves start from: the ``FEN`` tag, or the normal starting position without one.
+
+        let fen = self.tags.iter().find(|(name, _)| name == "FEN").map_or(START_FEN, |(_, value)| value.as_str());
+        Game::from_fen(String::from(fen)).map_err(PgnError::InvalidFen)
+    }
+
+    #[allow(dead_code)]
+    pub fn game(&self) -> Result<Game, PgnError> {
+        //! Replay the game, returns the final position.
+
+        let mut game = self.start_position()?;
+        for mve in self.moves.iter() {
+            if !game.is_legal(mve) {
+                return Err(PgnError::IllegalMove { game: None, notation: mve.long_algebraic_notation() });
+            }
+            game.do_move(mve);
+        }
+
+        Ok(game)
+    }
+
+    pub fn to_pgn(&self) -> Result<String, PgnError> {
+        //! Dump the game to PGN: the seven tag roster (``?`` for unknown values), the other tags and the movetext in SAN.
+
+        let mut pgn = String::new();
+        for name in SEVEN_TAG_ROSTER {
+            let value = match (name, self.tags.iter().find(|(tag_name, _)| tag_name == name)) {
+                ("Result", _) => self.result.as_str(),
+                (_, Some((_, value))) => value.as_str(),
+                ("Date", None) => "????.??.??",
+                (_, None) => "?",
+            };
+            pgn += &format!("[{} \"{}\"]\n", name, escape(value));
+        }
+        for (name, value) in self.tags.iter().filter(|(name, _)| !SEVEN_TAG_ROSTER.contains(&name.as_str())) {
+            pgn += &format!("[{} \"{}\"]\n", name, escape(value));
+        }
+        pgn += "\n";
+
+        let mut game = self.start_position()?;
+        let mut symbols = Vec::new();
+        for (idx, mve) in self.moves.iter().enumerate() {
+            if !game.is_legal(mve) {
+                return Err(PgnError::IllegalMove { game: None, notation: mve.long_algebraic_notation() });
+            }
+
+            // the move number goes in front of white's moves, and in front of the first move if black starts
+            if game.on_turn == Color::White {
+                symbols.push(format!("{}.", game.fullmove_counter));
+            } else if idx == 0 {
+                symbols.push(format!("{}...", game.fullmove_counter));
+            }
+            symbols.push(mve.to_san(&game));
+            game.do_move(mve);
+        }
+        symbols.push(self.result.clone());
+
+        let mut line = String::new();
+        for symbol in symbols {
+            if !line.is_empty() && line.len() + 1 + symbol.len() > MAX_LINE_LENGTH {
+                pgn += &line;
+                pgn += "\n";
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line += &symbol;
+        }
+        pgn += &line;
+        pgn += "\n";
+
+        Ok(pgn)
+    }
+}
+
+
+pub fn parse_pgn(text: &str) -> Result<Vec<PgnGame>, PgnError> {
+    //! Parse all the games of a PGN text.
+    //!
+    //! A game ends with its result, or when the tags of the next game start.
+
+    let mut games = Vec::new();
+    let mut pgn_game = PgnGame { tags: Vec::new(), moves: Vec::new(), result: String::from("*") };
+    let mut position: Option<Game> = None;
+    let mut variation_depth = 0;
+
+    for token in tokenize(text)? {
+        match token {
+            Token::VariationStart => variation_depth += 1,
+            Token::VariationEnd if variation_depth == 0 => return Err(PgnError::UnbalancedVariation),
+            Token::VariationEnd => variation_depth -= 1,
+            _ if variation_depth > 0 => {},
+            Token::Annotation => {},
+            Token::Tag(name, value) => {
+                // tags after moves belong to the next game
+                if position.is_some() {
+                    games.push(pgn_game);
+                    pgn_game = PgnGame { tags: Vec::new(), moves: Vec::new(), result: String::from("*") };
+                    position = None;
+                }
+                pgn_game.tags.push((name, value));
+            },
+            Token::Symbol(symbol) if RESULTS.contains(&symbol.as_str()) => {
+                pgn_game.result = symbol;
+                games.push(pgn_game);
+                pgn_game = PgnGame { tags: Vec::new(), moves: Vec::new(), result: String::from("*") };
+                position = None;
+            },
+            Token::Symbol(symbol) => {
+                // move numbers (``12.`` or ``12...``) may be written against the move, ``0-0`` isn't a move number
+                let number_length = symbol.len() - symbol.trim_start_matches(|chr: char| chr.is_ascii_digit()).len();
+                let notation = if number_length == symbol.len() || symbol[number_length..].starts_with('.') {
+                    symbol[number_length..].trim_start_matches('.')
+                } else {
+                    symbol.as_str()
+                };
+                if notation.is_empty() || notation.chars().all(|chr| chr == '!' || chr == '?') {
+                    continue;
+                }
+
+                if position.is_none() {
+                    position = Some(pgn_game.start_position()?);
+                }
+                let game = position.as_mut().unwrap();
+                let mve = Move::from_san(notation, game).ok_or_else(|| PgnError::IllegalMove { game: Some(games.len() + 1), notation: String::from(notation) })?;
+                game.do_move(&mve);
+                pgn_game.moves.push(mve);
+            },
+        }
+    }
+    if variation_depth != 0 {
+        return Err(PgnError::UnbalancedVariation);
+    }
+
+    // the last game doesn't need a result
+    if !pgn_game.tags.is_empty() || !pgn_game.moves.is_empty() {
+        games.push(pgn_game);
+    }
+
+    Ok(games)
+}
+
+#[allow(dead_code)]
+pub fn read_pgn_file(file_name: &str) -> Result<Vec<PgnGame>, String> {
+    //! Parse all the games in the specified PGN file.
+
+    let contents = fs::read_to_string(file_name).map_err(|error| format!("Can't read `{}`: {}", file_name, error))?;
+    parse_pgn(&contents).map_err(|error| format!("Can't parse `{}`: {}", file_name, error))
+}
+
+
+fn tokenize(text: &str) -> Result<Vec<Token>, PgnError> {
+    //! Split a PGN text into tokens, dropping the escape lines (starting with ``%``) and the whitespace.
+
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line_start = true;
+    while let Some(chr) = chars.next() {
+        match chr {
+            '%' if line_start => {
+                chars.by_ref().take_while(|chr| *chr != '\n').for_each(drop);
+                continue;
+            },
+            '\n' => {
+                line_start = true;
+                continue;
+            },
+            _ if chr.is_whitespace() => {},
+            ';' => {
+                chars.by_ref().take_while(|chr| *chr != '\n').for_each(drop);
+                tokens.push(Token::Annotation);
+                line_start = true;
+                continue;
+            },
+            '{' => {
+                if !chars.by_ref().any(|chr| chr == '}') {
+                    return Err(PgnError::UnterminatedComment);
+                }
+                tokens.push(Token::Annotation);
+            },
+            '(' => tokens.push(Token::VariationStart),
+            ')' => tokens.push(Token::VariationEnd),
+            '$' => {
+                while chars.next_if(|chr| chr.is_ascii_digit()).is_some() {}
+                tokens.push(Token::Annotation);
+            },
+            '[' => {
+                // a ``]`` within the quoted value doesn't end the tag
+                let mut tag = String::new();
+                let mut in_string = false;
+                let mut escaped = false;
+                loop {
+                    match chars.next() {
+                        Some(']') if !in_string => break,
+                        Some(chr) => {
+                            if in_string && chr == '\\' && !escaped {
+                                escaped = true;
+                            } else {
+                                in_string ^= chr == '"' && !escaped;
+                                escaped = false;
+                            }
+                            tag.push(chr);
+                        },
+                        None => return Err(PgnError::InvalidTag(tag)),
+                    }
+                }
+                tokens.push(parse_tag(&tag)?);
+            },
+            _ => {
+                let mut symbol = String::from(chr);
+                while let Some(chr) = chars.next_if(|chr| !chr.is_whitespace() && !"{}()[];$".contains(*chr)) {
+                    symbol.push(chr);
+                }
+                tokens.push(Token::Symbol(symbol));
+            },
+        }
+        line_start = false;
+    }
+
+    Ok(tokens)
+}
+
+fn parse_tag(tag: &str) -> Result<Token, PgnError> {
+    //! Parse the text between the brackets of a tag pair: ``Name "value"``.
+
+    let invalid = || PgnError::InvalidTag(format!("[{}]", tag));
+    let (name, value) = tag.trim().split_once(char::is_whitespace).ok_or_else(invalid)?;
+    let value = value.trim().strip_prefix('"').and_then(|value| value.strip_suffix('"')).ok_or_else(invalid)?;
+    if name.is_empty() || !name.chars().all(|chr| chr.is_ascii_alphanumeric() || chr == '_') {
+        return Err(invalid());
+    }
+
+    Ok(Token::Tag(String::from(name), value.replace("\\\"", "\"").replace("\\\\", "\\")))
+}
+
+fn escape(value: &str) -> String {
+    //! Escape the backslashes and quotes in the value of a tag.
+
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
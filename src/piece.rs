@@ -5,7 +5,7 @@
 use crate::consts::{ PieceType, Color, GamePhase };
 use crate::piece_scores::{ SCORE_KING, SCORE_QUEEN, SCORE_ROOK, SCORE_BISHOP, SCORE_KNIGHT, SCORE_PAWN };
 use crate::game::{ Game };
-use crate::utils::{ with_offsets, walk_offsets };
+use crate::bitboard::{ KNIGHT_ATTACKS, KING_ATTACKS, PAWN_ATTACKS, rook_attacks, bishop_attacks, queen_attacks, squares, square_index, index_to_cord, cord_bitboard };
 use crate::move_struct::{ Move };
 use crate::types::{ Cord };
 
@@ -30,12 +30,11 @@ impl Piece {
         //! Q = white queen  
         //! k = black king
 
-        let color;
-        if fen_letter.to_lowercase().to_string() == fen_letter.to_string() {
-            color = Color::Black;
+        let color = if fen_letter.to_lowercase().to_string() == fen_letter.to_string() {
+            Color::Black
         } else {
-            color = Color::White;
-        }
+            Color::White
+        };
 
         let piece_type = match fen_letter.to_lowercase().to_string().as_ref() {
             "k" => PieceType::King,
@@ -120,64 +119,76 @@ pub fn get_all_piece_moves(piece_type: PieceType, color: Color, pos: Cord, game:
     //! Get a list of all the moves a piece of this type and color can do from the specified position in the specified game.
 
     let mut moves: Vec<Move> = Vec::new();
-    
-    match piece_type {
-        PieceType::King => {
-            // standard moves
-            moves.extend(with_offsets(&color, pos, game.board, vec![[1, 0], [1, 1], [0, 1], [-1, 1], [-1, 0], [-1, -1], [0, -1], [1, -1]], false));
+    let from = square_index(pos);
+    let own = game.colors[color as usize];
+    let occupied = game.occupied();
 
+    let targets = match piece_type {
+        PieceType::King => {
             // castle moves
-            if !game.square_is_attacked(pos, if color == Color::White { Color::Black } else { Color::White }) {
-                let y = if color == Color::White { 0 } else { 7 };
-                let other_color = if color == Color::White { Color::Black } else { Color::White };
+            let y = if color == Color::White { 0 } else { 7 };
+            if pos == [4, y] && !game.square_is_attacked(pos, color.other()) {
+                let other_color = color.other();
                 let king = Piece { piece_type: PieceType::King, color};
                 let queen = Piece { piece_type: PieceType::Queen, color};
-                if game.castle.contains(&king) &&
-                    game.board[y][5].is_none() && !game.square_is_attacked([5, y], other_color) && game.board[y][6].is_none() && !game.square_is_attacked([6, pos[1]], other_color) {
-                        moves.push(Move { from: pos, to: [7, y], piece: Some(king)});
+                let rook = Some(Piece { piece_type: PieceType::Rook, color });
+                if game.castle.contains(&king) && game.board[y][7] == rook &&
+                    game.board[y][5].is_none() && !game.square_is_attacked([5, y], other_color) && game.board[y][6].is_none() && !game.square_is_attacked([6, y], other_color) {
+                        moves.push(Move { from: pos, to: [6, y], piece: Some(king)});
                 }
-                if game.castle.contains(&queen) &&
-                    game.board[y][1].is_none() && game.board[y][2].is_none() && !game.square_is_attacked([2, pos[1]], other_color) && game.board[y][3].is_none() && !game.square_is_attacked([3, pos[1]], other_color) {
-                        moves.push(Move { from: pos, to: [0, y], piece: Some(queen)});
+                if game.castle.contains(&queen) && game.board[y][0] == rook &&
+                    game.board[y][1].is_none() && game.board[y][2].is_none() && !game.square_is_attacked([2, y], other_color) && game.board[y][3].is_none() && !game.square_is_attacked([3, y], other_color) {
+                        moves.push(Move { from: pos, to: [2, y], piece: Some(queen)});
                 }
             }
+
+            // standard moves
+            KING_ATTACKS[from] & !own
         },
-        PieceType::Queen => moves.extend(walk_offsets(&color, pos, game.board, vec![[1, 0], [1, 1], [0, 1], [-1, 1], [-1, 0], [-1, -1], [0, -1], [1, -1]], None, true)),
-        PieceType::Bishop => moves.extend(walk_offsets(&color, pos, game.board, vec![[1, 1], [-1, 1], [-1, -1], [1, -1]], None, true)),
-        PieceType::Knight => moves.extend(with_offsets(&color, pos, game.board, vec![[-1, 2], [1, 2], [2, 1], [2, -1], [1, -2], [-1, -2], [-2, -1], [-2, 1]], false)),
-        PieceType::Rook => moves.extend(walk_offsets(&color, pos, game.board, vec![[1, 0], [0, 1], [-1, 0], [0, -1]], None, true)),
+        PieceType::Queen => queen_attacks(from, occupied) & !own,
+        PieceType::Bishop => bishop_attacks(from, occupied) & !own,
+        PieceType::Knight => KNIGHT_ATTACKS[from] & !own,
+        PieceType::Rook => rook_attacks(from, occupied) & !own,
         PieceType::Pawn => {
-            // can take pieces?
-            moves.extend(with_offsets(&color, pos, game.board, if color == Color::White { vec![[1, 1], [-1, 1]] } else { vec![[1, -1], [-1, -1]] }, true));
-
-            if (color == Color::White && pos[1] == 6 && game.board[7][pos[0]].is_none() ) || (color == Color::Black && pos[1] == 1 && game.board[0][pos[0]].is_none()) {
-                // promote
-                let to_y = if color == Color::White { 7 } else { 0 };
-                for piece_type in [PieceType::Queen, PieceType::Knight, PieceType::Rook, PieceType::Bishop] {
-                    moves.push(Move { from: pos, to: [pos[0], to_y], piece: Some(Piece { piece_type, color }) } );
+            let enemies = game.colors[color.other() as usize];
+            let mut targets = PAWN_ATTACKS[color as usize][from] & enemies;
+
+            // en passant
+            if let Some(en_passant_target_square) = game.en_passant_target_square {
+                targets |= PAWN_ATTACKS[color as usize][from] & cord_bitboard(en_passant_target_square);
+            }
+
+            // standard moves
+            let (forward, start_y): (i8, usize) = if color == Color::White { (1, 1) } else { (-1, 6) };
+            let one_step = [pos[0], (pos[1] as i8 + forward) as usize];
+            if occupied & cord_bitboard(one_step) == 0 {
+                targets |= cord_bitboard(one_step);
+
+                let two_steps = [pos[0], (pos[1] as i8 + 2 * forward) as usize];
+                if pos[1] == start_y && occupied & cord_bitboard(two_steps) == 0 {
+                    targets |= cord_bitboard(two_steps);
                 }
-            } else {
-                // standard moves
-                moves.extend(walk_offsets(
-                    &color,
-                    pos,
-                    game.board,
-                    if color == Color::White { vec![[0, 1]] } else { vec![[0, -1]] },
-                    if (color == Color::White && pos[1] == 1) || (color == Color::Black && pos[1] == 6) { Some(2) } else { Some(1) },
-                    false,
-                ));
             }
 
-            // en passant
-            if game.en_passant_target_square.is_some() {
-                for offset in if color == Color::White { vec![[1, 1], [-1, 1]] } else { vec![[1, -1], [-1, -1]] } {
-                    let cord = [(pos[0] as i8 + offset[0]) as usize, (pos[1] as i8 + offset[1]) as usize];
-                    if game.en_passant_target_square.unwrap() == cord {
-                        moves.push(Move::simple_new(pos, cord));
+            // moving to the last rank means promoting
+            let promote_y = if color == Color::White { 7 } else { 0 };
+            for to in squares(targets) {
+                let to = index_to_cord(to);
+                if to[1] == promote_y {
+                    for piece_type in [PieceType::Queen, PieceType::Knight, PieceType::Rook, PieceType::Bishop] {
+                        moves.push(Move { from: pos, to, piece: Some(Piece { piece_type, color }) } );
                     }
+                } else {
+                    moves.push(Move::simple_new(pos, to));
                 }
             }
+
+            0
         },
+    };
+
+    for to in squares(targets) {
+        moves.push(Move::simple_new(pos, index_to_cord(to)));
     }
 
     moves
@@ -0,0 +1,309 @@
+//! Structs for the state of a search.
+//! 
+//! ``SearchContext`` is shared by all the search threads, ``SearchThread`` is owned by one of them.
+
+use crate::consts::{ Color, Bound, CHECK_MATE_SCORE, MATE_BOUND, MAX_PLY };
+use crate::move_struct::{ Move };
+use crate::time_manager::{ SearchLimits, TimeManager, MOVE_OVERHEAD };
+use crate::transposition_table::{ TranspositionTable };
+use crate::move_ordering::{ MoveOrdering };
+use std::sync::{ Arc };
+use std::sync::atomic::{ AtomicBool, AtomicU64, AtomicUsize, Ordering };
+use std::time::{ Duration };
+use std::thread;
+
+
+/// Number of nodes a thread searches before it adds them to the total and checks the limits.
+const NODES_PER_CHECK: u64 = 1024;
+/// Time after which the move that is being searched is reported to the GUI.
+const CURRMOVE_DELAY: Duration = Duration::from_secs(1);
+/// Skill level at which the engine plays at full strength.
+pub const MAX_SKILL_LEVEL: u8 = 20;
+
+
+/// Settings of the engine that change how it searches.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchOptions {
+    /// Number of threads that search the root moves.
+    pub threads: usize,
+    /// Number of best moves (principal variations) to report.
+    pub multi_pv: usize,
+    /// Play moves from the openings database.
+    pub own_book: bool,
+    /// How much the engine dislikes a draw, in centipawns.
+    pub contempt: i64,
+    /// Time in milliseconds that is kept aside for the communication with the GUI.
+    pub move_overhead: u64,
+    /// From 0 (weakest) to ``MAX_SKILL_LEVEL`` (full strength).
+    pub skill_level: u8,
+    /// Let the opponent move twice, if the position is still good enough it isn't searched any further.
+    pub null_move: bool,
+    /// Search the quiet moves late in the move ordering less deep.
+    pub late_move_reductions: bool,
+    /// Skip the positions and quiet moves near the leaves that are too far below alpha or above beta.
+    pub futility_pruning: bool,
+    /// Search a ply deeper when in check.
+    pub check_extensions: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> SearchOptions {
+        SearchOptions {
+            threads: default_threads(),
+            multi_pv: 1,
+            own_book: true,
+            contempt: 0,
+            move_overhead: MOVE_OVERHEAD,
+            skill_level: MAX_SKILL_LEVEL,
+            null_move: true,
+            late_move_reductions: true,
+            futility_pruning: true,
+            check_extensions: true,
+        }
+    }
+}
+
+pub fn default_threads() -> usize {
+    //! One search thread per core.
+
+    thread::available_parallelism().map_or(1, |threads| threads.get())
+}
+
+
+/// A move of the searched position with the result of the last finished iteration.
+#[derive(Clone)]
+pub struct RootMove {
+    pub mve: Move,
+    pub score: i64,
+    /// Exact, or Upper when the search only proved the move is worse than the best move.
+    pub bound: Bound,
+    /// Principal variation, starting with the move itself.
+    pub pv: Vec<Move>,
+}
+
+
+/// Everything the search threads share.
+pub struct SearchContext {
+    pub limits: SearchLimits,
+    pub options: SearchOptions,
+    /// The color the search is done for.
+    pub root_color: Color,
+    pub time_manager: TimeManager,
+    pub transposition_table: Arc<TranspositionTable>,
+    /// Print [UCI](https://nl.wikipedia.org/wiki/Universal_Chess_Interface) ``info`` lines while searching.
+    pub uci_output: bool,
+    stop: AtomicBool,
+    nodes: AtomicU64,
+    /// The part of the nodes that was searched by the quiescence search.
+    qnodes: AtomicU64,
+    seldepth: AtomicUsize,
+    /// Beta cutoffs, and the part of them that was caused by the first move that was searched.
+    cutoffs: AtomicU64,
+    first_move_cutoffs: AtomicU64,
+}
+
+impl SearchContext {
+    pub fn new(limits: SearchLimits, options: SearchOptions, color: Color, transposition_table: &Arc<TranspositionTable>) -> SearchContext {
+        //! Start a new search with the specified limits and options for the specified color.
+
+        SearchContext {
+            time_manager: TimeManager::new(&limits, color, options.move_overhead),
+            limits,
+            options,
+            root_color: color,
+            transposition_table: Arc::clone(transposition_table),
+            uci_output: false,
+            stop: AtomicBool::new(false),
+            nodes: AtomicU64::new(0),
+            qnodes: AtomicU64::new(0),
+            seldepth: AtomicUsize::new(0),
+            cutoffs: AtomicU64::new(0),
+            first_move_cutoffs: AtomicU64::new(0),
+        }
+    }
+
+    pub fn stop(&self) {
+        //! Tell all the search threads to stop as soon as possible.
+
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    pub fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    pub fn draw_score(&self, color: Color) -> i64 {
+        //! Score of a draw for the specified color, the contempt makes the engine avoid (or look for) draws.
+
+        if color == self.root_color { -self.options.contempt } else { self.options.contempt }
+    }
+
+    pub fn nodes(&self) -> u64 {
+        //! Number of nodes searched by all the threads together (updated every ``NODES_PER_CHECK`` nodes per thread).
+
+        self.nodes.load(Ordering::Relaxed)
+    }
+
+    pub fn qnodes(&self) -> u64 {
+        //! Number of nodes searched by the quiescence search of all the threads together (updated when a thread finishes an iteration).
+
+        self.qnodes.load(Ordering::Relaxed)
+    }
+
+    pub fn first_move_cutoff_rate(&self) -> f64 {
+        //! The part of the beta cutoffs that was caused by the first move, a measure for how good the move ordering is.
+
+        let cutoffs = self.cutoffs.load(Ordering::Relaxed);
+        self.first_move_cutoffs.load(Ordering::Relaxed) as f64 / cutoffs.max(1) as f64
+    }
+
+    pub fn report_iteration(&self, depth: u8, root_moves: &[RootMove]) {
+        //! Print the results of a finished iteration, a line per principal variation.
+
+        if !self.uci_output {
+            return;
+        }
+
+        let time = self.time_manager.elapsed().as_millis().max(1) as u64;
+        let nodes = self.nodes();
+        let seldepth = self.seldepth.load(Ordering::Relaxed).max(depth as usize);
+        let hashfull = self.transposition_table.hashfull();
+        for (idx, root_move) in root_moves.iter().take(self.options.multi_pv.max(1)).enumerate() {
+            let pv = root_move.pv.iter().map(|mve| mve.long_algebraic_notation()).collect::<Vec<String>>().join(" ");
+            println!(
+                "info depth {} seldepth {} multipv {} score {} nodes {} nps {} time {} hashfull {} pv {}",
+                depth, seldepth, idx + 1, uci_score(root_move.score), nodes, nodes * 1000 / time, time, hashfull, pv,
+            );
+        }
+    }
+
+    pub fn report_game_over(&self, in_check: bool) {
+        //! Print the score of a position without legal moves: mated when in check, otherwise a stalemate.
+
+        if self.uci_output {
+            println!("info depth 0 score {}", if in_check { "mate 0" } else { "cp 0" });
+        }
+    }
+
+    pub fn report_current_move(&self, mve: &Move, number: usize) {
+        //! Print the root move a thread starts searching, only when the search takes a while.
+
+        if self.uci_output && self.time_manager.elapsed() >= CURRMOVE_DELAY {
+            println!("info currmove {} currmovenumber {}", mve.long_algebraic_notation(), number);
+        }
+    }
+
+    fn add_nodes(&self, nodes: u64) {
+        let total = self.nodes.fetch_add(nodes, Ordering::Relaxed) + nodes;
+        if self.limits.nodes.is_some_and(|limit| total >= limit) || self.time_manager.out_of_time() {
+            self.stop();
+        }
+    }
+}
+
+
+/// The part of the search state that belongs to a single thread.
+pub struct SearchThread<'a> {
+    pub context: &'a SearchContext,
+    /// If false the thread ignores the stop signal, so the search always has a result.
+    abortable: bool,
+    nodes: u64,
+    qnodes: u64,
+    seldepth: usize,
+    cutoffs: u64,
+    first_move_cutoffs: u64,
+    /// Principal variation per ply, collected while searching.
+    pv: Vec<Vec<Move>>,
+    pub ordering: MoveOrdering,
+}
+
+impl<'a> SearchThread<'a> {
+    pub fn new(context: &'a SearchContext, abortable: bool) -> SearchThread<'a> {
+        SearchThread {
+            context,
+            abortable,
+            nodes: 0,
+            qnodes: 0,
+            seldepth: 0,
+            cutoffs: 0,
+            first_move_cutoffs: 0,
+            pv: vec![Vec::new(); MAX_PLY + 1],
+            ordering: MoveOrdering::new(),
+        }
+    }
+
+    pub fn count_node(&mut self, ply: usize) {
+        //! Count a searched node at the specified ply, checking the limits of the search every now and then.
+
+        self.seldepth = self.seldepth.max(ply);
+        self.nodes += 1;
+        if self.nodes == NODES_PER_CHECK {
+            self.context.add_nodes(self.nodes);
+            self.nodes = 0;
+        }
+    }
+
+    pub fn count_qnode(&mut self, ply: usize) {
+        //! Count a node of the quiescence search, which is counted as a normal node as well.
+
+        self.qnodes += 1;
+        self.count_node(ply);
+    }
+
+    pub fn count_cutoff(&mut self, is_first_move: bool) {
+        self.cutoffs += 1;
+        self.first_move_cutoffs += is_first_move as u64;
+    }
+
+    pub fn stopped(&self) -> bool {
+        //! True if the thread has to stop searching, its results are incomplete from then on.
+
+        self.abortable && self.context.stopped()
+    }
+
+    pub fn clear_pv(&mut self, ply: usize) {
+        self.pv[ply].clear();
+    }
+
+    pub fn update_pv(&mut self, ply: usize, mve: Move) {
+        //! The move is the best move at the ply so far: the principal variation is the move followed by the one of the ply after it.
+
+        let (line, rest) = self.pv[ply..].split_first_mut().unwrap();
+        line.clear();
+        line.push(mve);
+        if let Some(next_line) = rest.first() {
+            line.extend_from_slice(next_line);
+        }
+    }
+
+    pub fn pv(&self, ply: usize) -> &[Move] {
+        &self.pv[ply]
+    }
+
+    pub fn finish(&mut self) {
+        //! Add the nodes that aren't counted yet to the total.
+
+        self.context.add_nodes(self.nodes);
+        self.context.qnodes.fetch_add(self.qnodes, Ordering::Relaxed);
+        self.context.seldepth.fetch_max(self.seldepth, Ordering::Relaxed);
+        self.context.cutoffs.fetch_add(self.cutoffs, Ordering::Relaxed);
+        self.context.first_move_cutoffs.fetch_add(self.first_move_cutoffs, Ordering::Relaxed);
+        self.nodes = 0;
+        self.qnodes = 0;
+        self.cutoffs = 0;
+        self.first_move_cutoffs = 0;
+    }
+}
+
+
+fn uci_score(score: i64) -> String {
+    //! Format a score for the GUI: ``cp <centipawns>`` or ``mate <moves>`` (negative when getting mated).
+
+    if score.abs() >= MATE_BOUND {
+        let plies = CHECK_MATE_SCORE - score.abs();
+        let moves = (plies + 1) / 2;
+        format!("mate {}", if score > 0 { moves } else { -moves })
+    } else {
+        format!("cp {}", score)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::game::{ Game };
+use crate::game::best_move::{ choose_root_move, sort_root_moves };
+use crate::consts::{ Bound, CHECK_MATE_SCORE };
+use crate::move_struct::{ Move };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchThread, SearchOptions, RootMove, MAX_SKILL_LEVEL };
+use crate::tests::{ search, search_context };
+
+
+/// Without the selective search, which gives up exact scores for speed.
+const FULL_WIDTH: SearchOptions = SearchOptions {
+    threads: 1,
+    multi_pv: 1,
+    own_book: false,
+    contempt: 0,
+    move_overhead: 0,
+    skill_level: MAX_SKILL_LEVEL,
+    null_move: false,
+    late_move_reductions: false,
+    futility_pruning: false,
+    check_extensions: false,
+};
+
+fn minimax(game: &mut Game, depth: u8, ply: usize, search: &mut SearchThread) -> i64 {
+    //! Plain negamax without any pruning, ending in the quiescence search like the real search does.
+
+    if game.is_draw(ply) {
+        return search.context.draw_score(game.on_turn);
+    }
+    if depth == 0 {
+        return game.quiescence(ply, -CHECK_MATE_SCORE, CHECK_MATE_SCORE, search);
+    }
+
+    let moves = game.legal_moves();
+    if moves.is_empty() {
+        return if game.is_in_check(game.on_turn) { -(CHECK_MATE_SCORE - ply as i64) } else { search.context.draw_score(game.on_turn) };
+    }
+
+    let mut best_score = -CHECK_MATE_SCORE;
+    for mve in moves {
+        let undo = game.make_move(&mve);
+        best_score = best_score.max(-minimax(game, depth - 1, ply + 1, search));
+        game.unmake_move(&mve, &undo);
+    }
+
+    best_score
+}
+
+fn assert_same_score_as_minimax(fen: &str, depth: u8) {
+    let mut game = Game::from_fen(String::from(fen)).unwrap();
+    let (_, best_moves) = search(&game, SearchLimits { infinite: true, ..SearchLimits::depth(depth) }, FULL_WIDTH);
+
+    let minimax_search = search_context(&game, SearchLimits::depth(depth), FULL_WIDTH);
+    let expected = minimax(&mut game, depth, 0, &mut SearchThread::new(&minimax_search, false));
+
+    assert_eq!(best_moves[0].score, expected, "{} at depth {}", fen, depth);
+    assert_eq!(best_moves[0].bound, Bound::Exact);
+    assert!(best_moves[1..].iter().all(|root_move| root_move.score <= expected));
+}
+
+#[test]
+fn same_score_as_minimax() {
+    for depth in 1..=3 {
+        assert_same_score_as_minimax("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", depth);
+        assert_same_score_as_minimax("8/2k5/3p4/p2P1p2/P2P1P2/8/8/4K3 w - - 0 1", depth);
+    }
+    // minimax takes too long for more captures
+    for depth in 1..=2 {
+        assert_same_score_as_minimax("r1bqkbnr/pppp1ppp/2n5/4p3/3PP3/5N2/PPP2PPP/RNBQKB1R b KQkq - 0 3", depth);
+    }
+    assert_same_score_as_minimax("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1", 4);
+}
+
+#[test]
+fn exact_scores_with_multiple_variations() {
+    let mut game = Game::from_fen(String::from("r1bqkbnr/pppp1ppp/2n5/4p3/3PP3/5N2/PPP2PPP/RNBQKB1R b KQkq - 0 3")).unwrap();
+    let search = search_context(&game, SearchLimits::depth(2), SearchOptions { multi_pv: 3, ..FULL_WIDTH });
+
+    for root_move in game.analyse(&search) {
+        let undo = game.make_move(&root_move.mve);
+        let expected = -minimax(&mut game, 1, 1, &mut SearchThread::new(&search, false));
+        game.unmake_move(&root_move.mve, &undo);
+
+        assert_eq!(root_move.bound, Bound::Exact);
+        assert_eq!(root_move.score, expected, "{}", root_move.mve.long_algebraic_notation());
+    }
+}
+
+#[test]
+fn upper_bound_tied_with_best_move() {
+    // with more threads a move searched with a null window can get the same score as the best move
+    let root_move = |notation: &str, score: i64, bound: Bound| {
+        let mve = Move::from_long_algebraic_notation(String::from(notation));
+        RootMove { mve, score, bound, pv: vec![mve] }
+    };
+    let mut root_moves = vec![root_move("e2e4", 30, Bound::Upper), root_move("g1f3", 10, Bound::Upper)];
+    choose_root_move(&mut root_moves, MAX_SKILL_LEVEL);
+    assert_eq!(root_moves[0].mve.long_algebraic_notation(), "e2e4");
+
+    let mut root_moves = vec![root_move("e2e4", 30, Bound::Upper), root_move("d2d4", 30, Bound::Exact), root_move("g1f3", 10, Bound::Upper)];
+    sort_root_moves(&mut root_moves);
+    assert_eq!(root_moves.iter().map(|root_move| root_move.mve.long_algebraic_notation()).collect::<Vec<_>>(), ["d2d4", "e2e4", "g1f3"]);
+    choose_root_move(&mut root_moves, MAX_SKILL_LEVEL);
+    assert_eq!(root_moves[0].mve.long_algebraic_notation(), "d2d4");
+}
@@ -0,0 +1,55 @@
+use crate::game::{ Game };
+use crate::bitboard::{ KNIGHT_ATTACKS, KING_ATTACKS, PAWN_ATTACKS, rook_attacks, bishop_attacks, sliding_attacks, square_index };
+use crate::consts::{ Color, PieceType };
+use crate::types::{ Bitboard };
+
+
+#[test]
+fn leaper_attack_tables() {
+    assert_eq!(KNIGHT_ATTACKS[square_index([0, 0])].count_ones(), 2);
+    assert_eq!(KNIGHT_ATTACKS[square_index([3, 3])].count_ones(), 8);
+    assert_eq!(KING_ATTACKS[square_index([7, 7])].count_ones(), 3);
+    assert_eq!(PAWN_ATTACKS[Color::White as usize][square_index([0, 1])], 1 << square_index([1, 2]));
+    assert_eq!(PAWN_ATTACKS[Color::Black as usize][square_index([4, 6])], (1 << square_index([3, 5])) | (1 << square_index([5, 5])));
+}
+
+#[test]
+fn magic_lookup_same_as_walking() {
+    // every subset of the empty board attacks covers every occupancy that matters for the square
+    let rook_directions = [[1, 0], [0, 1], [-1, 0], [0, -1]];
+    let bishop_directions = [[1, 1], [-1, 1], [-1, -1], [1, -1]];
+    for square in 0..64 {
+        for (directions, lookup) in [(rook_directions, rook_attacks as fn(usize, Bitboard) -> Bitboard), (bishop_directions, bishop_attacks)] {
+            let mask = sliding_attacks(square, 0, &directions);
+            let mut occupied: Bitboard = 0;
+            loop {
+                assert_eq!(lookup(square, occupied), sliding_attacks(square, occupied, &directions), "square {}, occupied {:#x}", square, occupied);
+                occupied = occupied.wrapping_sub(mask) & mask;
+                if occupied == 0 { break; }
+            }
+        }
+    }
+}
+
+#[test]
+fn rook_attacks_stop_at_blockers() {
+    assert_eq!(rook_attacks(square_index([0, 0]), 0).count_ones(), 14);
+    let blockers = (1 << square_index([0, 3])) | (1 << square_index([2, 0]));
+    assert_eq!(rook_attacks(square_index([0, 0]), blockers).count_ones(), 5);
+}
+
+#[test]
+fn bitboards_follow_board() {
+    let mut game = Game::from_fen(String::from("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")).unwrap();
+    for notation in ["e1c1", "h3g2", "d5e6", "g2h1q", "e6f7", "e8d8", "d2h6", "b4b3"] {
+        let mve = game.find_legal_move(notation).unwrap();
+        game.do_move(&mve);
+
+        let mut expected = game.clone();
+        expected.calculate_bitboards();
+        assert_eq!(game.pieces, expected.pieces);
+        assert_eq!(game.colors, expected.colors);
+    }
+
+    assert_eq!(game.pieces_of(PieceType::Queen, Color::Black).count_ones(), 2);
+}
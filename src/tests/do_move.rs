@@ -7,7 +7,7 @@ use crate::move_struct::{ Move };
 #[test]
 fn basic_move_test() {
     let mve = Move::simple_new([4, 1], [4, 3]);
-    let mut game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"));
+    let mut game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
     game.do_move(&mve);
 
     assert_eq!(game.board[3][4].unwrap().piece_type, PieceType::Pawn);
@@ -18,7 +18,7 @@ fn basic_move_test() {
 fn castle_move_test() {
     // queens side
     let mve = Move { from: [4, 0], to: [2, 0], piece: Some(Piece { color: Color::White, piece_type: PieceType::Queen }) };
-    let mut game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R3KBNR w KQkq - 0 1"));
+    let mut game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R3KBNR w KQkq - 0 1")).unwrap();
     game.do_move(&mve);
 
     assert!(game.board[0][0].is_none());
@@ -29,7 +29,7 @@ fn castle_move_test() {
 
     // kings side
     let mve = Move { from: [4, 0], to: [6, 0], piece: Some(Piece { color: Color::White, piece_type: PieceType::King }) };
-    let mut game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1"));
+    let mut game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1")).unwrap();
     game.do_move(&mve);
 
     assert!(game.board[0][7].is_none());
@@ -40,9 +40,9 @@ fn castle_move_test() {
 
 #[test]
 fn promote_move_test() {
-    for piece_type in vec![PieceType::Queen, PieceType::Knight, PieceType::Bishop, PieceType::Rook] {
-        let mve = Move { from: [0, 6], to: [0, 7], piece: Some(Piece { color: Color::White, piece_type: piece_type }) };
-        let mut game = Game::from_fen(String::from("8/P7/8/8/8/8/8/K1k5 w KQkq - 0 1"));
+    for piece_type in [PieceType::Queen, PieceType::Knight, PieceType::Bishop, PieceType::Rook] {
+        let mve = Move { from: [0, 6], to: [0, 7], piece: Some(Piece { color: Color::White, piece_type }) };
+        let mut game = Game::from_fen(String::from("8/P7/8/8/8/8/8/K1k5 w - - 0 1")).unwrap();
         game.do_move(&mve);
     
         assert_eq!(game.board[7][0].unwrap().piece_type, piece_type);
@@ -52,10 +52,10 @@ fn promote_move_test() {
 
 #[test]
 fn en_passant_test() {
-    let mve = Move { from: [1, 5], to: [0, 6], piece: Some(Piece { color: Color::White, piece_type: PieceType::Queen }) };
-    let mut game = Game::from_fen(String::from("8/8/pP6/8/8/8/8/K1k5 w KQkq a7 0 1"));
+    let mve = Move { from: [1, 4], to: [0, 5], piece: None };
+    let mut game = Game::from_fen(String::from("8/8/8/pP6/8/8/8/K1k5 w - a6 0 1")).unwrap();
     game.do_move(&mve);
 
-    assert!(game.board[0][5].is_none());
-    assert_eq!(game.board[6][0].unwrap().piece_type, PieceType::Pawn);
+    assert!(game.board[4][0].is_none());
+    assert_eq!(game.board[5][0].unwrap().piece_type, PieceType::Pawn);
 }
@@ -0,0 +1,87 @@
+use crate::game::{ Game };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchOptions };
+use crate::tests::{ search_context };
+
+
+fn game_after(fen_code: &str, moves: &[&str]) -> Game {
+    let mut game = Game::from_fen(String::from(fen_code)).unwrap();
+    for notation in moves {
+        let mve = game.find_legal_move(notation).unwrap();
+        game.do_move(&mve);
+    }
+    game
+}
+
+fn best_score(fen_code: &str, contempt: i64) -> i64 {
+    let game = Game::from_fen(String::from(fen_code)).unwrap();
+    let options = SearchOptions { contempt, ..Default::default() };
+
+    game.analyse(&search_context(&game, SearchLimits::depth(3), options))[0].score
+}
+
+const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[test]
+fn halfmove_clock() {
+    assert_eq!(game_after(START, &["g1f3", "g8f6"]).halfmove_clock, 2);
+    assert_eq!(game_after(START, &["g1f3", "g8f6", "e2e4"]).halfmove_clock, 0);
+    assert_eq!(game_after(START, &["e2e4", "d7d5", "g1f3", "d5e4"]).halfmove_clock, 0);
+    assert_eq!(Game::from_fen(String::from("8/8/8/4k3/8/8/8/R3K3 w - - 37 80")).unwrap().halfmove_clock, 37);
+}
+
+#[test]
+fn repetitions() {
+    let knights_back = ["g1f3", "g8f6", "f3g1", "f6g8"];
+    let game = game_after(START, &knights_back);
+    assert_eq!(game.repetitions(), 1);
+    assert!(!game.is_threefold_repetition());
+
+    // a repetition inside the search is already a draw, before the root it needs to be the third time
+    assert!(game.is_draw(4));
+    assert!(!game.is_draw(0));
+
+    let game = game_after(START, &[knights_back, knights_back].concat());
+    assert!(game.is_threefold_repetition());
+    assert!(game.is_draw(0));
+
+    // the same pieces after a pawn move isn't the same position
+    assert_eq!(game_after(START, &["g1f3", "g8f6", "f3g1", "f6g8", "e2e4"]).repetitions(), 0);
+}
+
+#[test]
+fn fifty_move_rule() {
+    assert!(!game_after("8/8/8/4k3/8/8/8/R3K3 w - - 98 80", &["a1a2"]).is_fifty_move_draw());
+    assert!(game_after("8/8/8/4k3/8/8/8/R3K3 w - - 99 80", &["a1a2"]).is_fifty_move_draw());
+}
+
+#[test]
+fn insufficient_material() {
+    for fen_code in [
+        "8/8/8/4k3/8/8/8/4K3 w - - 0 1",
+        "8/8/8/4k3/8/8/8/4KN2 w - - 0 1",
+        "8/8/8/4k3/8/8/8/4KB2 w - - 0 1",
+        "8/8/8/3bk3/8/8/8/4KB2 w - - 0 1",
+    ] {
+        assert!(Game::from_fen(String::from(fen_code)).unwrap().is_insufficient_material(), "{}", fen_code);
+    }
+
+    for fen_code in [
+        "8/8/8/4k3/8/8/8/4KNN1 w - - 0 1",
+        "8/8/8/2b1k3/8/8/8/4KB2 w - - 0 1",
+        "8/8/8/4k3/8/8/4P3/4K3 w - - 0 1",
+        "8/8/8/4k3/8/8/8/R3K3 w - - 0 1",
+    ] {
+        assert!(!Game::from_fen(String::from(fen_code)).unwrap().is_insufficient_material(), "{}", fen_code);
+    }
+}
+
+#[test]
+fn search_scores_draws() {
+    // every move ends the game by the fifty-move rule
+    assert_eq!(best_score("8/8/8/4k3/8/8/8/R3K3 w - - 99 80", 0), 0);
+    assert!(best_score("8/8/8/4k3/8/8/8/R3K3 w - - 0 80", 0) > 300);
+
+    // the contempt is how much the engine dislikes a draw
+    assert_eq!(best_score("8/8/8/4k3/8/8/8/4K3 w - - 0 1", 20), -20);
+}
@@ -0,0 +1,51 @@
+use crate::epd::{ EpdPosition };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchOptions };
+use crate::tests::{ search };
+
+
+fn notations(position: &EpdPosition) -> (Vec<String>, Vec<String>) {
+    (
+        position.best_moves.iter().map(|mve| mve.long_algebraic_notation()).collect(),
+        position.avoid_moves.iter().map(|mve| mve.long_algebraic_notation()).collect(),
+    )
+}
+
+#[test]
+fn parse_operations() {
+    let position = EpdPosition::from_epd("2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";").unwrap();
+    assert_eq!(position.id, "WAC.001");
+    assert_eq!(notations(&position), (vec![String::from("g3g6")], vec![]));
+    assert_eq!(position.game.to_fen(), "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1");
+
+    // several moves, clocks, long algebraic notation and a quoted ``;``
+    let position = EpdPosition::from_epd(
+        "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - c0 \"a; b\"; am Nxe5 d2d4; bm Bb5 Bc4; hmvc 2; fmvn 3"
+    ).unwrap();
+    assert_eq!(position.id, "");
+    assert_eq!(notations(&position), (vec![String::from("f1b5"), String::from("f1c4")], vec![String::from("f3e5"), String::from("d2d4")]));
+    assert_eq!(position.game.halfmove_clock, 2);
+    assert_eq!(position.game.fullmove_counter, 3);
+}
+
+#[test]
+fn invalid_lines() {
+    assert!(EpdPosition::from_epd("8/8/8/8/8/8/8/8 w - - bm Kg1;").is_err());
+    assert!(EpdPosition::from_epd("4k3/8/8/8/8/8/8/4K3 w - - bm Kg1;").is_err());
+    assert!(EpdPosition::from_epd("4k3/8/8/8/8/8/8/4K3 w - - id \"no moves\";").is_err());
+    assert!(EpdPosition::from_epd("4k3/8/8/8/8/8/8/4K3 w - - bm Kf1; id \"unterminated;").is_err());
+}
+
+#[test]
+fn solved() {
+    let position = EpdPosition::from_epd("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - bm Qh4#; id \"fools mate\";").unwrap();
+    let (_, root_moves) = search(&position.game, SearchLimits::depth(3), SearchOptions { own_book: false, ..Default::default() });
+    let best_move = root_moves[0].mve;
+
+    assert!(position.is_solved_by(&best_move));
+    assert!(!position.is_solved_by(&position.game.find_legal_move("d7d6").unwrap()));
+
+    let position = EpdPosition::from_epd("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - am Qh4#;").unwrap();
+    assert!(!position.is_solved_by(&best_move));
+    assert!(position.is_solved_by(&position.game.find_legal_move("d7d6").unwrap()));
+}
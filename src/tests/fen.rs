@@ -0,0 +1,79 @@
+use crate::game::{ Game };
+use crate::game::storage::{ FenError };
+use crate::consts::{ Color };
+use rand::seq::SliceRandom;
+
+
+#[test]
+fn to_fen_round_trip() {
+    for fen_code in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w Kq - 3 17",
+        "8/8/8/KPp4r/8/8/8/4k3 w - c6 0 42",
+        "4k3/8/8/8/8/8/8/4K3 b - - 99 120",
+    ] {
+        assert_eq!(Game::from_fen(String::from(fen_code)).unwrap().to_fen(), fen_code);
+    }
+}
+
+#[test]
+fn to_fen_after_moves() {
+    let mut game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
+    for notation in ["e2e4", "c7c5", "g1f3"] {
+        game.do_move(&game.find_legal_move(notation).unwrap());
+    }
+    assert_eq!(game.to_fen(), "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+
+    game.do_move(&game.find_legal_move("d7d5").unwrap());
+    assert_eq!(game.to_fen(), "rnbqkbnr/pp2pppp/8/2pp4/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq d6 0 3");
+}
+
+#[test]
+fn optional_clocks() {
+    let game = Game::from_fen(String::from("4k3/8/8/8/8/8/8/4K3 w - -")).unwrap();
+    assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+}
+
+#[test]
+fn invalid_fen() {
+    let error = |fen_code: &str| Game::from_fen(String::from(fen_code)).err();
+
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w"), Some(FenError::FieldCount(2)));
+    assert_eq!(error("4k3/8/8/8/8/8/4K3 w - - 0 1"), Some(FenError::RankCount(7)));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K4 w - - 0 1"), Some(FenError::RankLength { rank: 1, length: 9 }));
+    assert_eq!(error("4k3/8/8/8/8/8/ppp/4K3 w - - 0 1"), Some(FenError::RankLength { rank: 2, length: 3 }));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K2X w - - 0 1"), Some(FenError::UnknownPiece('X')));
+    assert_eq!(error("4k3/8/8/8/8/8/8/7R w - - 0 1"), Some(FenError::MissingKing(Color::White)));
+    assert_eq!(error("4k3/8/8/8/8/8/8/3KK3 w - - 0 1"), Some(FenError::TooManyKings(Color::White)));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 x - - 0 1"), Some(FenError::SideToMove(String::from("x"))));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K2R w KK - 0 1"), Some(FenError::CastlingRights(String::from("KK"))));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K2R w Kx - 0 1"), Some(FenError::CastlingRights(String::from("Kx"))));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K2R w Q - 0 1"), Some(FenError::CastlingRights(String::from("Q"))));
+    assert_eq!(error("4k3/8/8/8/8/8/8/3K3R w K - 0 1"), Some(FenError::CastlingRights(String::from("K"))));
+    assert_eq!(error("4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1"), Some(FenError::EnPassantSquare(String::from("e3"))));
+    assert_eq!(error("4k3/8/8/8/4P3/8/8/4K3 b - d3 0 1"), Some(FenError::EnPassantSquare(String::from("d3"))));
+    assert_eq!(error("4k3/8/8/8/4P3/8/8/4K3 b - e9 0 1"), Some(FenError::EnPassantSquare(String::from("e9"))));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w - - x 1"), Some(FenError::Clock(String::from("x"))));
+
+    assert!(error("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").is_none());
+    assert!(error("4k3/8/8/8/8/8/8/4K2R w K - 0 1").is_none());
+}
+
+#[test]
+fn random_games_round_trip() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..30 {
+        let mut game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
+        for _ in 0..120 {
+            let loaded = Game::from_fen(game.to_fen()).unwrap();
+            assert!(loaded == game, "{}", game.to_fen());
+            assert_eq!(loaded.hash, game.hash);
+
+            match game.legal_moves().choose(&mut rng) {
+                Some(mve) => game.do_move(mve),
+                None => break,
+            }
+        }
+    }
+}
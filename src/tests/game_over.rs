@@ -0,0 +1,40 @@
+use crate::game::{ Game };
+use crate::consts::{ CHECK_MATE_SCORE };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchOptions, RootMove };
+use crate::tests::{ search };
+
+
+fn all_root_moves(fen: &str, depth: u8) -> Vec<RootMove> {
+    let game = Game::from_fen(String::from(fen)).unwrap();
+    let options = SearchOptions { multi_pv: 64, ..Default::default() };
+    // the quiescence search finds mates in the first iteration, don't stop before the depth is reached
+    let limits = SearchLimits { infinite: true, ..SearchLimits::depth(depth) };
+
+    search(&game, limits, options).1
+}
+
+#[test]
+fn no_move_when_mated() {
+    let game = Game::from_fen(String::from("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")).unwrap();
+    let (_, root_moves) = search(&game, SearchLimits::depth(3), SearchOptions::default());
+
+    assert!(root_moves.is_empty());
+}
+
+#[test]
+fn no_move_when_stalemate() {
+    assert!(all_root_moves("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", 3).is_empty());
+}
+
+#[test]
+fn mate_and_stalemate_scores() {
+    let root_moves = all_root_moves("7k/8/6K1/8/8/8/8/5Q2 w - - 0 1", 2);
+    let score = |notation: &str| root_moves.iter().find(|root_move| root_move.mve.long_algebraic_notation() == notation).unwrap().score;
+
+    // Qf8 mates in one ply, Qf7 leaves black without moves but not in check
+    assert_eq!(root_moves[0].mve.long_algebraic_notation(), "f1f8");
+    assert_eq!(score("f1f8"), CHECK_MATE_SCORE - 1);
+    assert_eq!(score("f1f7"), 0);
+    assert!(score("f1f2") > 500);
+}
@@ -0,0 +1,73 @@
+use crate::game::{ Game };
+use crate::consts::{ Color };
+use crate::move_struct::{ Move };
+
+
+#[test]
+fn start_position_has_20_legal_moves() {
+    let game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
+    assert_eq!(game.legal_moves().len(), 20);
+}
+
+#[test]
+fn pinned_piece_cant_move() {
+    let game = Game::from_fen(String::from("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1")).unwrap();
+    let moves = game.legal_moves();
+
+    assert_eq!(moves.len(), 4);
+    assert!(moves.iter().all(|mve| mve.from != [4, 1]));
+}
+
+#[test]
+fn must_get_out_of_check() {
+    let game = Game::from_fen(String::from("4k3/8/8/8/8/8/3q4/4K3 w - - 0 1")).unwrap();
+    let mut moves = game.legal_moves().iter().map(|mve| mve.long_algebraic_notation()).collect::<Vec<String>>();
+    moves.sort();
+
+    assert!(game.is_in_check(Color::White));
+    assert_eq!(moves, vec!["e1d2", "e1f1"]);
+}
+
+#[test]
+fn en_passant_pin() {
+    let game = Game::from_fen(String::from("8/8/8/KPp4r/8/8/8/4k3 w - c6 0 1")).unwrap();
+    assert!(game.find_legal_move("b5c6").is_none());
+    assert!(game.find_legal_move("b5b6").is_some());
+}
+
+#[test]
+fn no_castling_through_check() {
+    let game = Game::from_fen(String::from("4k3/8/8/8/8/5r2/8/R3K2R w KQ - 0 1")).unwrap();
+    assert!(game.find_legal_move("e1g1").is_none());
+    assert!(game.find_legal_move("e1c1").is_some());
+}
+
+#[test]
+fn promote_by_taking() {
+    let game = Game::from_fen(String::from("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1")).unwrap();
+    let promotions = game.legal_moves().into_iter().filter(|mve| mve.from == [0, 6]).count();
+    assert_eq!(promotions, 8);
+    assert!(game.find_legal_move("a7b8n").is_some());
+}
+
+#[test]
+fn checkmate_has_no_legal_moves() {
+    let game = Game::from_fen(String::from("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")).unwrap();
+    assert!(game.legal_moves().is_empty());
+    assert!(game.is_in_check(Color::White));
+}
+
+#[test]
+fn stalemate_has_no_legal_moves() {
+    let game = Game::from_fen(String::from("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1")).unwrap();
+    assert!(game.legal_moves().is_empty());
+    assert!(!game.is_in_check(Color::Black));
+}
+
+#[test]
+fn legal_line_stops_at_illegal_move() {
+    let game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
+    let line = ["e2e4", "e7e5", "e1e3", "d7d5"].map(|mve| Move::from_long_algebraic_notation(String::from(mve)));
+
+    assert!(game.legal_line(&line) == line[..2]);
+}
@@ -0,0 +1,54 @@
+use crate::game::{ Game };
+
+
+fn assert_same_game(game: &Game, other: &Game) {
+    assert!(game.board == other.board);
+    assert_eq!(game.on_turn, other.on_turn);
+    assert!(game.castle == other.castle);
+    assert_eq!(game.en_passant_target_square, other.en_passant_target_square);
+    assert_eq!(game.moves, other.moves);
+    assert_eq!(game.score_white, other.score_white);
+    assert_eq!(game.hash, other.hash);
+    assert_eq!(game.hash_history, other.hash_history);
+    assert_eq!(game.halfmove_clock, other.halfmove_clock);
+    assert_eq!(game.fullmove_counter, other.fullmove_counter);
+}
+
+fn assert_unmake_restores(game: &mut Game, depth: u8) {
+    if depth == 0 {
+        return;
+    }
+
+    for mve in game.legal_moves() {
+        let before = game.clone();
+        let undo = game.make_move(&mve);
+        assert_unmake_restores(game, depth - 1);
+        game.unmake_move(&mve, &undo);
+        assert_same_game(game, &before);
+    }
+}
+
+#[test]
+fn unmake_restores_game() {
+    for fen_code in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1",
+    ] {
+        let mut game = Game::from_fen(String::from(fen_code)).unwrap();
+        assert_unmake_restores(&mut game, 2);
+    }
+}
+
+#[test]
+fn make_move_same_as_do_move() {
+    let mut game = Game::from_fen(String::from("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")).unwrap();
+    for mve in game.legal_moves() {
+        let mut other = game.clone();
+        other.do_move(&mve);
+        let undo = game.make_move(&mve);
+        assert_same_game(&game, &other);
+        game.unmake_move(&mve, &undo);
+    }
+}
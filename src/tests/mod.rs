@@ -1,3 +1,50 @@
+#[allow(clippy::module_inception)]
 mod tests;
 mod do_move;
 mod get_move_type;
+mod legal_moves;
+mod perft;
+mod make_unmake;
+mod bitboard;
+mod zobrist;
+mod transposition_table;
+mod time_manager;
+mod uci_command;
+mod uci_options;
+mod multi_pv;
+mod draw;
+mod game_over;
+mod status;
+mod fen;
+mod san;
+mod pgn;
+mod epd;
+mod quiescence;
+mod move_ordering;
+mod alpha_beta;
+mod selective_search;
+mod see;
+
+
+use crate::game::{ Game };
+use crate::openings::{ OpeningsDatabase };
+use crate::transposition_table::{ TranspositionTable };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchContext, SearchOptions, RootMove };
+use std::sync::{ Arc };
+
+
+pub fn search_context(game: &Game, limits: SearchLimits, options: SearchOptions) -> SearchContext {
+    //! A search for the side on turn, with its own small transposition table.
+
+    SearchContext::new(limits, options, game.on_turn, &Arc::new(TranspositionTable::new(1)))
+}
+
+pub fn search(game: &Game, limits: SearchLimits, options: SearchOptions) -> (SearchContext, Vec<RootMove>) {
+    //! Search the game without an openings database, returns the finished search (for its statistics) and the root moves.
+
+    let search = search_context(game, limits, options);
+    let root_moves = game.get_best_moves(&search, &OpeningsDatabase::new());
+
+    (search, root_moves)
+}
@@ -0,0 +1,84 @@
+use crate::game::{ Game };
+use crate::consts::{ Color };
+use crate::move_struct::{ Move };
+use crate::move_ordering::{ MoveOrdering, is_losing_capture };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchOptions };
+use crate::tests::{ search };
+
+
+// the white queen on d1 and pawn on b4 can take the rook on d5 and the knight on c5, the king can take nothing
+const FEN: &str = "4k3/8/8/2nr4/1P6/8/8/3QK3 w - - 0 1";
+
+fn sorted_moves(ordering: &MoveOrdering, hash_move: Option<Move>) -> Vec<String> {
+    let game = Game::from_fen(String::from(FEN)).unwrap();
+    let mut moves = game.get_all_moves(game.on_turn);
+    ordering.sort_moves(&game, &mut moves, hash_move, 0);
+
+    moves.iter().map(|mve| mve.long_algebraic_notation()).collect()
+}
+
+#[test]
+fn captures_by_most_valuable_victim() {
+    let moves = sorted_moves(&MoveOrdering::new(), None);
+
+    assert_eq!(moves[..2], ["d1d5", "b4c5"]);
+}
+
+#[test]
+fn losing_captures_last() {
+    // the rook on d5 is defended by the pawn
+    let game = Game::from_fen(String::from("4k3/8/2p5/3r4/8/8/8/3QK3 w - - 0 1")).unwrap();
+    let mut moves = game.get_all_moves(game.on_turn);
+    let scores = MoveOrdering::new().sort_moves(&game, &mut moves, None, 0);
+
+    assert_eq!(scores.len(), moves.len());
+    assert_eq!(moves.last().unwrap().long_algebraic_notation(), "d1d5");
+    assert!(is_losing_capture(*scores.last().unwrap()));
+    assert!(!scores[..scores.len() - 1].iter().any(|score| is_losing_capture(*score)));
+}
+
+#[test]
+fn hash_move_first() {
+    let moves = sorted_moves(&MoveOrdering::new(), Some(Move::simple_new([4, 0], [4, 1])));
+
+    assert_eq!(moves[..3], ["e1e2", "d1d5", "b4c5"]);
+}
+
+#[test]
+fn killer_moves_and_history_after_captures() {
+    let game = Game::from_fen(String::from(FEN)).unwrap();
+    let killer = Move::simple_new([4, 0], [5, 1]);
+    let quiet = Move::simple_new([3, 0], [0, 3]);
+    let mut ordering = MoveOrdering::new();
+    ordering.update(&game, &quiet, 0, 4, &[]);
+    ordering.update(&game, &killer, 0, 2, &[quiet]);
+
+    assert_eq!(ordering.killers(0), [Some(killer), Some(quiet)]);
+    assert_eq!(ordering.history(Color::White, &quiet), 16 - 4);
+    assert_eq!(ordering.history(Color::White, &killer), 4);
+    assert_eq!(sorted_moves(&ordering, None)[..4], ["d1d5", "b4c5", "e1f2", "d1a4"]);
+}
+
+#[test]
+fn history_stays_bounded() {
+    let game = Game::from_fen(String::from(FEN)).unwrap();
+    let good = Move::simple_new([4, 0], [5, 1]);
+    let bad = Move::simple_new([3, 0], [0, 3]);
+    let mut ordering = MoveOrdering::new();
+    for _ in 0..10000 {
+        ordering.update(&game, &good, 0, 64, &[bad]);
+    }
+
+    assert!((1 << 19..=1 << 20).contains(&ordering.history(Color::White, &good)));
+    assert!((-(1 << 20)..=-(1 << 19)).contains(&ordering.history(Color::White, &bad)));
+}
+
+#[test]
+fn counts_cutoffs() {
+    let game = Game::from_fen(String::from("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")).unwrap();
+    let (search, _) = search(&game, SearchLimits::depth(3), SearchOptions::default());
+
+    let rate = search.first_move_cutoff_rate();
+    assert!(rate > 0.5 && rate <= 1.0);
+}
@@ -0,0 +1,45 @@
+use crate::game::{ Game };
+use crate::consts::{ MATE_BOUND };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchOptions, RootMove };
+use crate::tests::{ search, search_context };
+
+
+fn analyse(fen: &str, depth: u8, multi_pv: usize) -> Vec<RootMove> {
+    let game = Game::from_fen(String::from(fen)).unwrap();
+    let options = SearchOptions { multi_pv, ..Default::default() };
+
+    game.analyse(&search_context(&game, SearchLimits::depth(depth), options))
+}
+
+#[test]
+fn returns_best_variations() {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    let game = Game::from_fen(String::from(fen)).unwrap();
+    let lines = analyse(fen, 3, 4);
+
+    assert_eq!(lines.len(), 4);
+    for (idx, line) in lines.iter().enumerate() {
+        assert_eq!(line.pv[0], line.mve);
+        assert_eq!(game.legal_line(&line.pv), line.pv);
+        assert!(lines[idx + 1..].iter().all(|other| other.mve != line.mve && other.score <= line.score));
+    }
+}
+
+#[test]
+fn mate_and_second_best_move() {
+    // Qh5xf7 mates, the other moves don't
+    let lines = analyse("r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4", 3, 2);
+
+    assert_eq!(lines[0].mve.long_algebraic_notation(), "h5f7");
+    assert!(lines[0].score >= MATE_BOUND);
+    assert!(lines[1].score < MATE_BOUND);
+}
+
+#[test]
+fn no_book_move_with_multiple_variations() {
+    let game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
+    let (_, root_moves) = search(&game, SearchLimits::depth(2), SearchOptions { multi_pv: 3, ..Default::default() });
+
+    assert_eq!(root_moves.len(), 20);
+}
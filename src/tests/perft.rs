@@ -0,0 +1,71 @@
+use crate::game::{ Game };
+
+// Node counts from https://www.chessprogramming.org/Perft_Results and the TalkChess perft suite.
+
+fn assert_perft(fen_code: &str, depth: u8, nodes: u64) {
+    let game = Game::from_fen(String::from(fen_code)).unwrap();
+    assert_eq!(game.perft(depth), nodes, "perft({}) of `{}`", depth, fen_code);
+}
+
+#[test]
+fn perft_start_position() {
+    assert_perft("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 1, 20);
+    assert_perft("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 2, 400);
+    assert_perft("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 3, 8902);
+    assert_perft("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 4, 197281);
+}
+
+#[test]
+fn perft_kiwipete() {
+    assert_perft("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 1, 48);
+    assert_perft("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 2, 2039);
+    assert_perft("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 3, 97862);
+}
+
+#[test]
+fn perft_standard_positions() {
+    assert_perft("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 4, 43238);
+    assert_perft("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", 3, 9467);
+    assert_perft("r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1", 3, 9467);
+    assert_perft("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", 2, 1486);
+    assert_perft("r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10", 2, 2079);
+}
+
+#[test]
+fn perft_en_passant() {
+    assert_perft("3k4/3p4/8/K1P4r/8/8/8/8 b - - 0 1", 4, 10138);
+    assert_perft("8/8/4k3/8/2p5/8/B2P2K1/8 w - - 0 1", 4, 10276);
+    assert_perft("8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1", 4, 13931);
+}
+
+#[test]
+fn perft_castling() {
+    assert_perft("5k2/8/8/8/8/8/8/4K2R w K - 0 1", 4, 6399);
+    assert_perft("3k4/8/8/8/8/8/8/R3K3 w Q - 0 1", 4, 7418);
+    assert_perft("r3k2r/1b4bq/8/8/8/8/7B/R3K2R w KQkq - 0 1", 2, 1141);
+    assert_perft("r3k2r/8/3Q4/8/8/5q2/8/R3K2R b KQkq - 0 1", 2, 1494);
+}
+
+#[test]
+fn perft_promotion() {
+    assert_perft("2K2r2/4P3/8/8/8/8/8/3k4 w - - 0 1", 4, 19174);
+    assert_perft("4k3/1P6/8/8/8/8/K7/8 w - - 0 1", 4, 2661);
+    assert_perft("8/P1k5/K7/8/8/8/8/8 w - - 0 1", 4, 1329);
+}
+
+#[test]
+fn perft_checks_and_stalemates() {
+    assert_perft("8/8/1P2K3/8/2n5/1q6/8/5k2 b - - 0 1", 3, 5160);
+    assert_perft("K1k5/8/P7/8/8/8/8/8 w - - 0 1", 6, 2217);
+    assert_perft("8/k1P5/8/1K6/8/8/8/8 w - - 0 1", 4, 926);
+    assert_perft("8/8/2k5/5q2/5n2/8/5K2/8 b - - 0 1", 3, 6559);
+}
+
+#[test]
+fn perft_divide_sums_to_perft() {
+    let game = Game::from_fen(String::from("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")).unwrap();
+    let divide = game.perft_divide(2);
+
+    assert_eq!(divide.len(), 48);
+    assert_eq!(divide.iter().map(|(_mve, nodes)| nodes).sum::<u64>(), 2039);
+}
@@ -0,0 +1,141 @@
+use crate::game::{ Game };
+use crate::move_struct::{ Move };
+use crate::pgn::{ PgnGame, PgnError, parse_pgn };
+
+
+const GAMES: &str = r#"% exported by hand
+[Event "Casual \"blitz\" game"]
+[Site "?"]
+[White "Beth"]
+[Black "Human"]
+[Result "0-1"]
+
+1. f3 {a bad start} e5 $2 2.g4?? (2. e4 Nc6 (2... d5) 3. Nc3) Qh4# 0-1
+
+[Event "Second"]
+[SetUp "1"]
+[FEN "r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 20"]
+
+20... O-O ; castling black
+21. 0-0-0 Rf2 1/2-1/2
+"#;
+
+#[test]
+fn parse_games() {
+    let games = parse_pgn(GAMES).unwrap();
+    assert_eq!(games.len(), 2);
+
+    assert_eq!(games[0].tag("Event"), Some("Casual \"blitz\" game"));
+    assert_eq!(games[0].tag("White"), Some("Beth"));
+    assert_eq!(games[0].moves.len(), 4);
+    assert_eq!(games[0].result, "0-1");
+    assert!(games[0].game().unwrap().status().is_over());
+
+    assert_eq!(games[1].tag("Event"), Some("Second"));
+    assert_eq!(games[1].result, "1/2-1/2");
+    let moves = games[1].moves.iter().map(|mve| mve.long_algebraic_notation()).collect::<Vec<String>>();
+    assert_eq!(moves, vec!["e8g8", "e1c1", "f8f2"]);
+    assert_eq!(games[1].game().unwrap().to_fen(), "r5k1/8/8/8/8/8/5r2/2KR3R w - - 3 22");
+}
+
+#[test]
+fn parse_without_tags_or_result() {
+    let games = parse_pgn("1. e4 e5 2. Nf3").unwrap();
+
+    assert_eq!(games.len(), 1);
+    assert!(games[0].tags.is_empty());
+    assert_eq!(games[0].moves.len(), 3);
+    assert_eq!(games[0].result, "*");
+    assert!(parse_pgn("").unwrap().is_empty());
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(parse_pgn("1. e4 e5 2. Ke3").err(), Some(PgnError::IllegalMove { game: Some(1), notation: String::from("Ke3") }));
+    assert_eq!(parse_pgn("1. e4 * 1. d4 d5 2. Nd2 *").err(), None);
+    assert_eq!(parse_pgn("1. e4 * 1. d4 Nd2 *").err(), Some(PgnError::IllegalMove { game: Some(2), notation: String::from("Nd2") }));
+    assert_eq!(parse_pgn("1. e4 * 1. d4 Nd2 *").unwrap_err().to_string(), "illegal move `Nd2` in game 2");
+    assert_eq!(parse_pgn("1. e4 {no end").err(), Some(PgnError::UnterminatedComment));
+    assert_eq!(parse_pgn("1. e4 (1. d4 e5").err(), Some(PgnError::UnbalancedVariation));
+    assert_eq!(parse_pgn("1. e4 ) e5").err(), Some(PgnError::UnbalancedVariation));
+    assert!(matches!(parse_pgn("[Event]\n1. e4").err(), Some(PgnError::InvalidTag(_))));
+    assert!(matches!(parse_pgn("[FEN \"8/8/8/8/8/8/8/8 w - - 0 1\"]\n1. e4").err(), Some(PgnError::InvalidFen(_))));
+}
+
+#[test]
+fn write_game() {
+    let start = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
+    let mut game = start.clone();
+    for notation in ["f3", "e5", "g4", "Qh4#"] {
+        game.do_move(&Move::from_san(notation, &game).unwrap());
+    }
+
+    let mut pgn_game = PgnGame::new(&start, &game.moves);
+    pgn_game.set_tag("White", "Beth \\o/");
+    pgn_game.set_tag("Annotator", "Beth");
+    assert_eq!(pgn_game.result, "0-1");
+    assert_eq!(pgn_game.to_pgn().unwrap(), concat!(
+        "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n[White \"Beth \\\\o/\"]\n[Black \"?\"]\n[Result \"0-1\"]\n",
+        "[Annotator \"Beth\"]\n\n",
+        "1. f3 e5 2. g4 Qh4# 0-1\n",
+    ));
+
+    let read = parse_pgn(&pgn_game.to_pgn().unwrap()).unwrap();
+    assert_eq!(read[0].moves, pgn_game.moves);
+    assert_eq!(read[0].tag("White"), Some("Beth \\o/"));
+}
+
+#[test]
+fn write_from_position() {
+    let start = Game::from_fen(String::from("4k3/8/8/8/8/8/4P3/4K3 b - - 3 40")).unwrap();
+    let mut game = start.clone();
+    for notation in ["Kd7", "e4", "Kc6"] {
+        game.do_move(&Move::from_san(notation, &game).unwrap());
+    }
+
+    let pgn = PgnGame::new(&start, &game.moves).to_pgn().unwrap();
+    assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 3 40\"]\n"));
+    assert!(pgn.ends_with("\n40... Kd7 41. e4 Kc6 *\n"));
+}
+
+#[test]
+fn write_parsed_castling() {
+    let start = Game::from_fen(String::from("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1")).unwrap();
+    let moves: Vec<Move> = ["e1g1", "e8c8"].iter().map(|notation| Move::from_long_algebraic_notation(String::from(*notation))).collect();
+
+    let pgn_game = PgnGame::new(&start, &moves);
+    assert!(pgn_game.to_pgn().unwrap().ends_with("\n1. O-O O-O-O *\n"));
+    assert_eq!(pgn_game.game().unwrap().to_fen(), "2kr3r/8/8/8/8/8/8/R4RK1 w - - 2 2");
+}
+
+#[test]
+fn write_illegal_move() {
+    let start = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
+    let moves: Vec<Move> = ["e2e4", "e1e2"].iter().map(|notation| Move::from_long_algebraic_notation(String::from(*notation))).collect();
+
+    let pgn_game = PgnGame::new(&start, &moves);
+    let error = PgnError::IllegalMove { game: None, notation: String::from("e1e2") };
+    assert_eq!(pgn_game.game().err(), Some(error.clone()));
+    assert_eq!(pgn_game.to_pgn().err(), Some(error.clone()));
+    assert_eq!(error.to_string(), "illegal move `e1e2`");
+}
+
+#[test]
+fn long_games_wrap() {
+    let start = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
+    let mut game = start.clone();
+    for _ in 0..10 {
+        for notation in ["Nf3", "Nf6", "Ng1", "Ng8"] {
+            game.do_move(&Move::from_san(notation, &game).unwrap());
+        }
+    }
+
+    let pgn_game = PgnGame::new(&start, &game.moves);
+    assert_eq!(pgn_game.result, "1/2-1/2");
+
+    let pgn = pgn_game.to_pgn().unwrap();
+    let movetext = pgn.split("\n\n").nth(1).unwrap();
+    assert!(movetext.lines().count() > 1);
+    assert!(movetext.lines().all(|line| line.len() <= 80));
+    assert_eq!(parse_pgn(&pgn).unwrap()[0].moves, pgn_game.moves);
+}
@@ -0,0 +1,32 @@
+use crate::game::{ Game };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchOptions };
+use crate::tests::{ search };
+
+
+fn best_move(fen: &str, depth: u8) -> String {
+    let game = Game::from_fen(String::from(fen)).unwrap();
+    let (_, root_moves) = search(&game, SearchLimits::depth(depth), SearchOptions::default());
+
+    root_moves[0].mve.long_algebraic_notation()
+}
+
+#[test]
+fn doesnt_take_defended_pawn() {
+    // Qxd5 is answered by cxd5, which only the quiescence search sees at depth 1
+    assert_ne!(best_move("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1", 1), "d1d5");
+}
+
+#[test]
+fn takes_undefended_piece() {
+    assert_eq!(best_move("4k3/8/8/3r4/8/8/8/3QK3 w - - 0 1", 1), "d1d5");
+}
+
+#[test]
+fn counts_quiescence_nodes() {
+    let game = Game::from_fen(String::from("r1bqkbnr/pppp1ppp/2n5/4p3/3PP3/5N2/PPP2PPP/RNBQKB1R b KQkq - 0 3")).unwrap();
+    let (search, _) = search(&game, SearchLimits::depth(2), SearchOptions::default());
+
+    assert!(search.qnodes() > 0);
+    assert!(search.qnodes() < search.nodes());
+}
@@ -0,0 +1,83 @@
+use crate::game::{ Game };
+use crate::move_struct::{ Move };
+
+
+fn san(fen_code: &str, notation: &str) -> String {
+    let game = Game::from_fen(String::from(fen_code)).unwrap();
+    game.find_legal_move(notation).unwrap().to_san(&game)
+}
+
+fn from_san(fen_code: &str, notation: &str) -> Option<String> {
+    let game = Game::from_fen(String::from(fen_code)).unwrap();
+    Move::from_san(notation, &game).map(|mve| mve.long_algebraic_notation())
+}
+
+const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn to_san() {
+    assert_eq!(san(START, "e2e4"), "e4");
+    assert_eq!(san(START, "g1f3"), "Nf3");
+    assert_eq!(san(KIWIPETE, "e5f7"), "Nxf7");
+    assert_eq!(san(KIWIPETE, "d5e6"), "dxe6");
+    assert_eq!(san(KIWIPETE, "e1g1"), "O-O");
+    assert_eq!(san(KIWIPETE, "e1c1"), "O-O-O");
+    assert_eq!(san("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), "exd6");
+    assert_eq!(san("3rk3/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7d8n"), "exd8=N");
+    assert_eq!(san("3r4/4P1k1/8/8/8/8/8/4K3 w - - 0 1", "e7e8q"), "e8=Q");
+    assert_eq!(san("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7d8q"), "exd8=Q+");
+    assert_eq!(san("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2", "d8h4"), "Qh4#");
+}
+
+#[test]
+fn disambiguation() {
+    assert_eq!(san("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1", "b1d2"), "Nbd2");
+    assert_eq!(san("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1", "f1d2"), "Nfd2");
+    assert_eq!(san("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1", "a1a3"), "R1a3");
+    assert_eq!(san("4k3/8/8/8/8/Q1Q5/8/Q1Q1K3 w - - 0 1", "a1b2"), "Qa1b2");
+    assert_eq!(san("4k3/8/8/8/8/Q1Q5/8/Q1Q1K3 w - - 0 1", "a1b1"), "Qab1");
+
+    // the other knight is pinned, so it doesn't count
+    assert_eq!(san("4k3/4r3/8/8/8/8/4N3/2N1K3 w - - 0 1", "c1d3"), "Nd3");
+}
+
+#[test]
+fn from_san_variations() {
+    assert_eq!(from_san(START, "Nf3"), Some(String::from("g1f3")));
+    assert_eq!(from_san(START, "Ng1f3"), Some(String::from("g1f3")));
+    assert_eq!(from_san(START, "e4!?"), Some(String::from("e2e4")));
+    assert_eq!(from_san(KIWIPETE, "0-0-0"), Some(String::from("e1c1")));
+    assert_eq!(from_san(KIWIPETE, "Nxf7+"), Some(String::from("e5f7")));
+    assert_eq!(from_san("3r4/4P1k1/8/8/8/8/8/4K3 w - - 0 1", "e8Q"), Some(String::from("e7e8q")));
+    assert_eq!(from_san("3r4/4P1k1/8/8/8/8/8/4K3 w - - 0 1", "exd8=R"), Some(String::from("e7d8r")));
+
+    // a promotion has to name the piece, and ambiguous or illegal moves aren't anything
+    assert_eq!(from_san("3r4/4P1k1/8/8/8/8/8/4K3 w - - 0 1", "e8"), None);
+    assert_eq!(from_san("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1", "Nd2"), None);
+    assert_eq!(from_san(START, "Ke2"), None);
+    // castling is only written as O-O or O-O-O
+    assert_eq!(from_san(KIWIPETE, "Kg1"), None);
+    assert_eq!(from_san(KIWIPETE, "Kc1"), None);
+    assert_eq!(from_san(KIWIPETE, "O-O"), Some(String::from("e1g1")));
+    assert_eq!(from_san(START, "e5"), None);
+    assert_eq!(from_san(START, "Zz9"), None);
+    assert_eq!(from_san(START, ""), None);
+}
+
+#[test]
+fn round_trip() {
+    for fen_code in [
+        START,
+        KIWIPETE,
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "4k3/8/8/8/8/Q1Q5/8/Q1Q1K3 w - - 0 1",
+    ] {
+        let game = Game::from_fen(String::from(fen_code)).unwrap();
+        for mve in game.legal_moves() {
+            assert_eq!(Move::from_san(&mve.to_san(&game), &game), Some(mve), "{} {}", fen_code, mve.to_san(&game));
+        }
+    }
+}
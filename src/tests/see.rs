@@ -0,0 +1,43 @@
+use crate::game::{ Game };
+
+
+fn see(fen: &str, notation: &str) -> i64 {
+    let game = Game::from_fen(String::from(fen)).unwrap();
+    let mve = game.find_legal_move(notation).unwrap();
+
+    game.see(&mve)
+}
+
+#[test]
+fn undefended_piece() {
+    assert_eq!(see("4k3/8/8/4p3/8/8/8/4RK2 w - - 0 1", "e1e5"), 100);
+    assert_eq!(see("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), 100);
+}
+
+#[test]
+fn defended_piece() {
+    assert_eq!(see("4k3/8/3p4/4p3/8/8/8/4QK2 w - - 0 1", "e1e5"), -800);
+    // the knight is worth more than the pawn that takes back
+    assert_eq!(see("4k3/8/3p4/4n3/8/8/8/4RK2 w - - 0 1", "e1e5"), -200);
+    assert_eq!(see("4k3/8/3p4/4n3/3P4/8/8/4K3 w - - 0 1", "d4e5"), 200);
+}
+
+#[test]
+fn x_ray_attackers() {
+    assert_eq!(see("4r1k1/8/8/4p3/8/8/4R3/6K1 w - - 0 1", "e2e5"), -400);
+    // the rook behind the rook takes back as well
+    assert_eq!(see("4r1k1/8/8/4p3/8/8/4R3/4R1K1 w - - 0 1", "e2e5"), 100);
+}
+
+#[test]
+fn king_cant_take_defended_piece() {
+    assert_eq!(see("6k1/5p2/8/8/8/8/8/5QK1 w - - 0 1", "f1f7"), -800);
+    assert_eq!(see("6k1/5p2/8/8/2B5/8/8/5QK1 w - - 0 1", "f1f7"), 100);
+}
+
+#[test]
+fn promotion() {
+    assert_eq!(see("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8q"), 800);
+    assert_eq!(see("r3k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7a8q"), 1300);
+    assert_eq!(see("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8q"), -100);
+}
@@ -0,0 +1,57 @@
+use crate::game::{ Game };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchOptions };
+use crate::tests::{ search };
+
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn best_move_and_nodes(fen: &str, depth: u8, options: SearchOptions) -> (String, u64) {
+    let game = Game::from_fen(String::from(fen)).unwrap();
+    let (search, root_moves) = search(&game, SearchLimits::depth(depth), SearchOptions { threads: 1, ..options });
+
+    (root_moves[0].mve.long_algebraic_notation(), search.nodes())
+}
+
+fn without_selective_search() -> SearchOptions {
+    SearchOptions { null_move: false, late_move_reductions: false, futility_pruning: false, check_extensions: false, ..Default::default() }
+}
+
+#[test]
+fn null_move_keeps_position() {
+    let mut game = Game::from_fen(String::from("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3")).unwrap();
+    let original = game.clone();
+
+    let undo = game.make_null_move();
+    assert_eq!(game.to_fen(), "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3");
+    assert_eq!(game.hash, game.calculate_hash());
+    game.unmake_null_move(&undo);
+
+    assert!(game == original);
+    assert_eq!(game.hash, original.hash);
+}
+
+#[test]
+fn fewer_nodes() {
+    let (_, all_nodes) = best_move_and_nodes(KIWIPETE, 5, SearchOptions::default());
+    let (_, full_width_nodes) = best_move_and_nodes(KIWIPETE, 5, without_selective_search());
+
+    assert!(all_nodes < full_width_nodes, "{} nodes with and {} without selective search", all_nodes, full_width_nodes);
+}
+
+#[test]
+fn each_option_finds_mate() {
+    // Qd8+ Bxd8, Re8# (mate in two)
+    let fen = "r1b2k1r/ppp1bppp/8/1B1Q4/5q2/2P5/PPP2PPP/R3R1K1 w - - 1 1";
+    let options = [
+        SearchOptions::default(),
+        SearchOptions { null_move: true, ..without_selective_search() },
+        SearchOptions { late_move_reductions: true, ..without_selective_search() },
+        SearchOptions { futility_pruning: true, ..without_selective_search() },
+        SearchOptions { check_extensions: true, ..without_selective_search() },
+    ];
+
+    for options in options {
+        assert_eq!(best_move_and_nodes(fen, 4, options.clone()).0, "d5d8", "{:?}", options);
+    }
+}
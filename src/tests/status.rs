@@ -0,0 +1,76 @@
+use crate::game::{ Game };
+use crate::consts::{ Color, GameStatus };
+use crate::move_struct::{ Move };
+
+
+fn status(fen_code: &str) -> GameStatus {
+    Game::from_fen(String::from(fen_code)).unwrap().status()
+}
+
+#[test]
+fn ongoing() {
+    let game_status = status("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+    assert_eq!(game_status, GameStatus::Ongoing);
+    assert!(!game_status.is_over());
+    assert_eq!(game_status.result(), "*");
+}
+
+#[test]
+fn checkmate() {
+    let game = Game::from_fen(String::from("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")).unwrap();
+
+    assert_eq!(game.status(), GameStatus::Checkmate(Color::Black));
+    assert_eq!(game.status().winner(), Some(Color::Black));
+    assert_eq!(game.status().result(), "0-1");
+    assert!(game.is_check());
+    assert_eq!(game.checkers(), 1 << (3 * 8 + 7));
+
+    // the mate counts, even when the fifty moves are complete
+    assert_eq!(status("7k/6Q1/6K1/8/8/8/8/8 b - - 120 80"), GameStatus::Checkmate(Color::White));
+}
+
+#[test]
+fn draws() {
+    assert_eq!(status("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"), GameStatus::Stalemate);
+    assert_eq!(status("8/8/8/4k3/8/8/8/4KN2 w - - 0 1"), GameStatus::InsufficientMaterial);
+    assert_eq!(status("8/8/8/4k3/8/8/8/R3K3 w - - 99 80"), GameStatus::Ongoing);
+    assert_eq!(status("8/8/8/4k3/8/8/8/R3K3 w - - 100 80"), GameStatus::FiftyMoveRule);
+    assert_eq!(status("8/8/8/4k3/8/8/8/R3K3 w - - 150 80"), GameStatus::SeventyFiveMove);
+    assert_eq!(status("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").result(), "1/2-1/2");
+
+    let mut game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")).unwrap();
+    for notation in ["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1"] {
+        game.do_move(&Move::from_long_algebraic_notation(String::from(notation)));
+        assert_eq!(game.status(), GameStatus::Ongoing);
+    }
+    game.do_move(&Move::from_long_algebraic_notation(String::from("f6g8")));
+    assert_eq!(game.status(), GameStatus::ThreefoldRepetition);
+}
+
+#[test]
+fn checkers_and_legal_moves() {
+    // double check by the rook and the knight
+    let game = Game::from_fen(String::from("4k3/8/8/8/8/5n2/8/r3K3 w - - 0 1")).unwrap();
+    assert_eq!(game.checkers().count_ones(), 2);
+    assert!(game.is_legal(&Move::from_long_algebraic_notation(String::from("e1e2"))));
+    assert!(!game.is_legal(&Move::from_long_algebraic_notation(String::from("e1d1"))));
+    assert!(!game.is_legal(&Move::from_long_algebraic_notation(String::from("e1f1"))));
+
+    let game = Game::from_fen(String::from("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1")).unwrap();
+    assert!(!game.is_check());
+    assert_eq!(game.checkers(), 0);
+    assert!(!game.is_legal(&Move::from_long_algebraic_notation(String::from("e2c3"))));
+    assert!(!game.is_legal(&Move::from_long_algebraic_notation(String::from("e2e4"))));
+    assert!(game.is_legal(&Move::from_long_algebraic_notation(String::from("e1d1"))));
+
+    // castling, the parsed moves don't carry the castling piece
+    let game = Game::from_fen(String::from("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1")).unwrap();
+    assert!(game.is_legal(&Move::from_long_algebraic_notation(String::from("e1g1"))));
+    assert!(game.is_legal(&Move::from_long_algebraic_notation(String::from("e1c1"))));
+
+    // can't castle through an attacked square or without the right
+    let game = Game::from_fen(String::from("r3k2r/8/8/8/8/8/5r2/R3K2R w Kkq - 0 1")).unwrap();
+    assert!(!game.is_legal(&Move::from_long_algebraic_notation(String::from("e1g1"))));
+    assert!(!game.is_legal(&Move::from_long_algebraic_notation(String::from("e1c1"))));
+}
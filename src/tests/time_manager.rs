@@ -0,0 +1,89 @@
+use crate::game::{ Game };
+use crate::consts::{ Color };
+use crate::openings::{ OpeningsDatabase };
+use crate::time_manager::{ SearchLimits, TimeManager, MOVE_OVERHEAD };
+use crate::search::{ SearchOptions };
+use crate::tests::{ search, search_context };
+use std::sync::{ Arc };
+use std::time::{ Duration, Instant };
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn time_budgets() {
+    let movetime = TimeManager::new(&SearchLimits { movetime: Some(1000), ..Default::default() }, Color::White, MOVE_OVERHEAD);
+    assert_eq!(movetime.soft_limit(), Some(Duration::from_millis(1000 - MOVE_OVERHEAD)));
+    assert_eq!(movetime.hard_limit(), movetime.soft_limit());
+
+    // black uses its own clock, the budget stays well within the time left
+    let limits = SearchLimits { wtime: Some(1000), btime: Some(60000), binc: Some(1000), ..Default::default() };
+    let clock = TimeManager::new(&limits, Color::Black, MOVE_OVERHEAD);
+    let (soft, hard) = (clock.soft_limit().unwrap(), clock.hard_limit().unwrap());
+    assert!(soft < hard && hard < Duration::from_millis(60000 / 2));
+    assert!(TimeManager::new(&limits, Color::White, MOVE_OVERHEAD).hard_limit().unwrap() < Duration::from_millis(1000));
+
+    // the last move before the time control may use almost everything
+    let last_move = TimeManager::new(&SearchLimits { wtime: Some(5000), movestogo: Some(1), ..Default::default() }, Color::White, MOVE_OVERHEAD);
+    assert_eq!(last_move.hard_limit(), Some(Duration::from_millis(5000 - MOVE_OVERHEAD)));
+
+    let infinite = TimeManager::new(&SearchLimits { wtime: Some(1000), infinite: true, ..Default::default() }, Color::White, MOVE_OVERHEAD);
+    assert_eq!(infinite.hard_limit(), None);
+    assert!(infinite.can_start_iteration() && !infinite.out_of_time());
+}
+
+#[test]
+fn search_stops_on_time() {
+    let game = Game::from_fen(String::from(KIWIPETE)).unwrap();
+
+    let start = Instant::now();
+    let (_, root_moves) = search(&game, SearchLimits { movetime: Some(300), ..Default::default() }, SearchOptions::default());
+    assert!(start.elapsed() < Duration::from_millis(1500));
+    assert!(game.legal_moves().contains(&root_moves[0].mve));
+}
+
+#[test]
+fn search_stops_on_nodes() {
+    let game = Game::from_fen(String::from(KIWIPETE)).unwrap();
+    let (search, root_moves) = search(&game, SearchLimits { nodes: Some(20000), ..Default::default() }, SearchOptions::default());
+
+    assert!(search.stopped());
+    assert!(game.legal_moves().contains(&root_moves[0].mve));
+}
+
+#[test]
+fn search_stops_on_stop_signal() {
+    let game = Game::from_fen(String::from(KIWIPETE)).unwrap();
+    let search = Arc::new(search_context(&game, SearchLimits { infinite: true, ..Default::default() }, SearchOptions::default()));
+
+    let stopper_search = Arc::clone(&search);
+    let stopper = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(300));
+        stopper_search.stop();
+    });
+
+    let start = Instant::now();
+    let best_move = game.get_best_move(&search, &OpeningsDatabase::new()).unwrap();
+    stopper.join().unwrap();
+    assert!(start.elapsed() < Duration::from_millis(1500));
+    assert!(game.legal_moves().contains(&best_move));
+}
+
+#[test]
+fn ponder_ignores_time_until_ponderhit() {
+    let game = Game::from_fen(String::from(KIWIPETE)).unwrap();
+    let limits = SearchLimits { movetime: Some(200), ponder: true, ..Default::default() };
+    let search = Arc::new(search_context(&game, limits, SearchOptions::default()));
+
+    let thread_search = Arc::clone(&search);
+    let handle = std::thread::spawn(move || game.get_best_moves(&thread_search, &OpeningsDatabase::new()));
+
+    std::thread::sleep(Duration::from_millis(600));
+    assert!(!handle.is_finished());
+
+    // the time counts from the ponderhit
+    let ponderhit = Instant::now();
+    search.time_manager.ponderhit();
+    let best_moves = handle.join().unwrap();
+    assert!(ponderhit.elapsed() < Duration::from_millis(1500));
+    assert!(!best_moves[0].pv.is_empty());
+}
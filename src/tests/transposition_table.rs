@@ -0,0 +1,93 @@
+use crate::game::{ Game };
+use crate::consts::{ Bound, CHECK_MATE_SCORE };
+use crate::move_struct::{ Move };
+use crate::transposition_table::{ TranspositionTable };
+
+
+#[test]
+fn store_and_probe() {
+    let transposition_table = TranspositionTable::new(1);
+    let mve = Move::simple_new([4, 1], [4, 3]);
+    transposition_table.store(0x1234_5678, 5, Bound::Lower, -150, 2, Some(mve));
+
+    let entry = transposition_table.probe(0x1234_5678, 2).unwrap();
+    assert_eq!(entry.depth, 5);
+    assert_eq!(entry.bound, Bound::Lower);
+    assert_eq!(entry.score, -150);
+    assert_eq!(entry.best_move, Some(mve));
+
+    assert!(transposition_table.probe(0x8765_4321, 2).is_none());
+    transposition_table.clear();
+    assert!(transposition_table.probe(0x1234_5678, 2).is_none());
+}
+
+#[test]
+fn mate_scores_relative_to_ply() {
+    let transposition_table = TranspositionTable::new(1);
+
+    // mate at ply 5, found at ply 3, is mate at ply 3 when found at ply 1
+    transposition_table.store(1, 2, Bound::Exact, CHECK_MATE_SCORE - 5, 3, None);
+    assert_eq!(transposition_table.probe(1, 1).unwrap().score, CHECK_MATE_SCORE - 3);
+
+    transposition_table.store(2, 2, Bound::Exact, -CHECK_MATE_SCORE + 4, 2, None);
+    assert_eq!(transposition_table.probe(2, 6).unwrap().score, -CHECK_MATE_SCORE + 8);
+}
+
+#[test]
+fn deepest_entry_is_kept() {
+    // a table this small has a single bucket
+    let transposition_table = TranspositionTable::new(0);
+    transposition_table.store(1, 8, Bound::Exact, 10, 0, None);
+    transposition_table.store(2, 2, Bound::Exact, 20, 0, None);
+    transposition_table.store(3, 1, Bound::Exact, 30, 0, None);
+
+    assert_eq!(transposition_table.probe(1, 0).unwrap().score, 10);
+    assert!(transposition_table.probe(2, 0).is_none());
+    assert_eq!(transposition_table.probe(3, 0).unwrap().score, 30);
+
+    // entries of an earlier search are replaced first
+    transposition_table.new_search();
+    transposition_table.store(4, 1, Bound::Exact, 40, 0, None);
+    assert!(transposition_table.probe(1, 0).is_none());
+    assert_eq!(transposition_table.probe(4, 0).unwrap().score, 40);
+}
+
+#[test]
+fn keeps_best_move_without_new_one() {
+    let transposition_table = TranspositionTable::new(1);
+    let mve = Move::simple_new([6, 0], [5, 2]);
+    transposition_table.store(7, 3, Bound::Exact, 0, 0, Some(mve));
+    transposition_table.store(7, 4, Bound::Upper, -20, 0, None);
+
+    assert_eq!(transposition_table.probe(7, 0).unwrap().best_move, Some(mve));
+}
+
+#[test]
+fn pack_moves() {
+    for fen_code in [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1",
+        "1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/p7/1R2K3 b - - 0 1",
+    ] {
+        let game = Game::from_fen(String::from(fen_code)).unwrap();
+        for mve in game.legal_moves() {
+            assert_eq!(Move::from_u16(mve.to_u16()), mve);
+        }
+    }
+}
+
+#[test]
+fn hashfull_counts_current_search() {
+    let transposition_table = TranspositionTable::new(1);
+    assert_eq!(transposition_table.hashfull(), 0);
+
+    for hash in 0..100_000u64 {
+        transposition_table.store(hash.wrapping_mul(0x9e37_79b9_7f4a_7c15), 1, Bound::Exact, 0, 0, None);
+    }
+    assert!(transposition_table.hashfull() > 400);
+
+    // entries of an earlier search don't count
+    transposition_table.new_search();
+    assert_eq!(transposition_table.hashfull(), 0);
+}
@@ -0,0 +1,83 @@
+use crate::uci_command::{ UciCommand, GoCommand, UciError, parse_command, START_FEN };
+use crate::time_manager::{ SearchLimits };
+
+
+#[test]
+fn parse_position() {
+    assert_eq!(parse_command("position startpos moves e2e4 e7e5"), Ok(UciCommand::Position {
+        fen: String::from(START_FEN),
+        moves: vec![String::from("e2e4"), String::from("e7e5")],
+    }));
+
+    let kiwipete = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    assert_eq!(parse_command(&format!("position fen {} moves e1g1", kiwipete)), Ok(UciCommand::Position {
+        fen: String::from(kiwipete),
+        moves: vec![String::from("e1g1")],
+    }));
+    assert_eq!(parse_command(&format!("position fen {}", kiwipete)), Ok(UciCommand::Position { fen: String::from(kiwipete), moves: vec![] }));
+
+    assert_eq!(parse_command("position fen 8/8/8/8/8/8/8/K1k5 b - -"), Ok(UciCommand::Position {
+        fen: String::from("8/8/8/8/8/8/8/K1k5 b - - 0 1"),
+        moves: vec![],
+    }));
+
+    assert!(matches!(parse_command("position fen"), Err(UciError::InvalidValue { .. })));
+    assert!(matches!(parse_command("position"), Err(UciError::MissingArgument { .. })));
+    assert!(matches!(parse_command("position e2e4"), Err(UciError::InvalidValue { .. })));
+}
+
+#[test]
+fn parse_go() {
+    let go = parse_command("go wtime 60000 btime -50 winc 1000 binc 500 movestogo 20 ponder");
+    assert_eq!(go, Ok(UciCommand::Go(GoCommand {
+        limits: SearchLimits {
+            wtime: Some(60000),
+            btime: Some(0),
+            winc: Some(1000),
+            binc: Some(500),
+            movestogo: Some(20),
+            ponder: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    })));
+
+    let go = parse_command("go searchmoves e2e4 a7a8q infinite depth 7");
+    assert_eq!(go, Ok(UciCommand::Go(GoCommand {
+        limits: SearchLimits { depth: Some(7), infinite: true, ..Default::default() },
+        searchmoves: vec![String::from("e2e4"), String::from("a7a8q")],
+        ..Default::default()
+    })));
+
+    assert_eq!(parse_command("go perft 3"), Ok(UciCommand::Go(GoCommand { perft: Some(3), ..Default::default() })));
+    assert!(matches!(parse_command("go"), Ok(UciCommand::Go(go)) if go.limits.is_empty()));
+    assert!(matches!(parse_command("go nodes abc"), Err(UciError::InvalidValue { .. })));
+    assert!(matches!(parse_command("go movetime"), Err(UciError::MissingArgument { .. })));
+}
+
+#[test]
+fn parse_setoption_and_register() {
+    assert_eq!(parse_command("setoption name Move Overhead value 100"), Ok(UciCommand::SetOption {
+        name: String::from("Move Overhead"),
+        value: Some(String::from("100")),
+    }));
+    assert_eq!(parse_command("setoption name Clear Hash"), Ok(UciCommand::SetOption { name: String::from("Clear Hash"), value: None }));
+    assert!(parse_command("setoption value 1").is_err());
+
+    assert_eq!(parse_command("register later"), Ok(UciCommand::Register { name: None, code: None }));
+    assert_eq!(parse_command("register name Stefan MK code 4359874324"), Ok(UciCommand::Register {
+        name: Some(String::from("Stefan MK")),
+        code: Some(String::from("4359874324")),
+    }));
+}
+
+#[test]
+fn parse_simple_commands() {
+    assert_eq!(parse_command("joho debug on"), Ok(UciCommand::Debug(true)));
+    assert_eq!(parse_command("debug off"), Ok(UciCommand::Debug(false)));
+    assert!(parse_command("debug").is_err());
+    assert_eq!(parse_command("  isready  "), Ok(UciCommand::IsReady));
+    assert_eq!(parse_command("ponderhit"), Ok(UciCommand::PonderHit));
+    assert_eq!(parse_command(""), Err(UciError::Empty));
+    assert_eq!(parse_command("hello world"), Err(UciError::UnknownCommand(String::from("hello"))));
+}
@@ -0,0 +1,70 @@
+use crate::uci::{ EngineOptions, OptionValue };
+use crate::game::{ Game };
+use crate::time_manager::{ SearchLimits };
+use crate::search::{ SearchOptions, MAX_SKILL_LEVEL };
+use crate::tests::{ search };
+
+
+#[test]
+fn declares_options() {
+    let options = EngineOptions::new();
+    let declarations = options.options.iter().map(|option| option.declaration()).collect::<Vec<String>>();
+
+    assert!(declarations.contains(&String::from("option name Hash type spin default 16 min 1 max 4096")));
+    assert!(declarations.contains(&String::from("option name Ponder type check default false")));
+    assert!(declarations.contains(&String::from("option name SyzygyPath type string default <empty>")));
+    assert!(declarations.contains(&String::from("option name Null Move Pruning type check default true")));
+    assert_eq!(declarations.len(), 18);
+}
+
+#[test]
+fn set_options() {
+    let mut options = EngineOptions::new();
+
+    assert_eq!(options.set("hash", Some("64")), Ok("Hash"));
+    assert_eq!(options.spin("Hash"), 64);
+    assert!(options.set("Hash", Some("0")).is_err());
+    assert!(options.set("Hash", Some("big")).is_err());
+    assert_eq!(options.spin("Hash"), 64);
+
+    assert_eq!(options.set("Move Overhead", Some("200")), Ok("Move Overhead"));
+    assert_eq!(options.set("OwnBook", Some("false")), Ok("OwnBook"));
+    assert!(options.set("OwnBook", Some("no")).is_err());
+    assert_eq!(options.set("SyzygyPath", Some("<empty>")), Ok("SyzygyPath"));
+    assert_eq!(options.value("SyzygyPath"), &OptionValue::String(String::new()));
+    assert!(options.set("UCI_Chess960", Some("true")).is_err());
+    assert!(options.set("Unknown Option", Some("1")).is_err());
+
+    assert_eq!(options.set("late move reductions", Some("false")), Ok("Late Move Reductions"));
+    assert_eq!(options.set("Check Extensions", Some("false")), Ok("Check Extensions"));
+
+    let search_options = options.search_options();
+    assert_eq!(search_options.move_overhead, 200);
+    assert!(!search_options.own_book);
+    assert!(search_options.null_move && search_options.futility_pruning);
+    assert!(!search_options.late_move_reductions && !search_options.check_extensions);
+}
+
+#[test]
+fn limit_strength() {
+    let mut options = EngineOptions::new();
+    options.set("Skill Level", Some("10")).unwrap();
+    assert_eq!(options.search_options().skill_level, 10);
+
+    // the elo overrules the skill level when the strength is limited
+    options.set("UCI_LimitStrength", Some("true")).unwrap();
+    options.set("UCI_Elo", Some("800")).unwrap();
+    assert_eq!(options.search_options().skill_level, 0);
+    options.set("UCI_Elo", Some("2400")).unwrap();
+    assert_eq!(options.search_options().skill_level, MAX_SKILL_LEVEL);
+}
+
+#[test]
+fn search_with_options() {
+    let game = Game::from_fen(String::from("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")).unwrap();
+
+    for options in [SearchOptions { threads: 1, ..Default::default() }, SearchOptions { skill_level: 0, threads: 3, ..Default::default() }] {
+        let (_, root_moves) = search(&game, SearchLimits::depth(3), options);
+        assert!(game.legal_moves().contains(&root_moves[0].mve));
+    }
+}
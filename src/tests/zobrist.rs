@@ -0,0 +1,57 @@
+use crate::game::{ Game };
+
+
+fn game_after(fen_code: &str, moves: &[&str]) -> Game {
+    let mut game = Game::from_fen(String::from(fen_code)).unwrap();
+    for notation in moves.iter() {
+        let mve = game.find_legal_move(notation).unwrap();
+        game.do_move(&mve);
+    }
+    game
+}
+
+#[test]
+fn incremental_hash_same_as_calculated() {
+    let game = game_after(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        &["a2a4", "b4a3", "e1g1", "h3g2", "f3f6", "g2f1q", "a1f1", "e8c8"],
+    );
+    assert_eq!(game.hash, game.calculate_hash());
+}
+
+#[test]
+fn transposition_same_hash() {
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    let game = game_after(start, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    assert_eq!(game.hash, Game::from_fen(String::from(start)).unwrap().hash);
+
+    let game = game_after(start, &["e2e4", "e7e5", "d2d4"]);
+    let other = game_after(start, &["d2d4", "e7e5", "e2e4"]);
+    assert_eq!(game.hash, other.hash);
+}
+
+#[test]
+fn side_to_move_changes_hash() {
+    let white = Game::from_fen(String::from("4k3/8/8/8/8/8/8/4K3 w - - 0 1")).unwrap();
+    let black = Game::from_fen(String::from("4k3/8/8/8/8/8/8/4K3 b - - 0 1")).unwrap();
+    assert_ne!(white.hash, black.hash);
+}
+
+#[test]
+fn castle_rights_change_hash() {
+    let with = Game::from_fen(String::from("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1")).unwrap();
+    let without = Game::from_fen(String::from("r3k2r/8/8/8/8/8/8/R3K2R w Qkq - 0 1")).unwrap();
+    assert_ne!(with.hash, without.hash);
+}
+
+#[test]
+fn en_passant_only_hashed_when_possible() {
+    // no black pawn can take on e3
+    let game = game_after("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", &["e2e4"]);
+    assert_eq!(game.hash, Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1")).unwrap().hash);
+
+    // the black pawn on d4 can take on e3
+    let with = Game::from_fen(String::from("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1")).unwrap();
+    let without = Game::from_fen(String::from("4k3/8/8/8/3pP3/8/8/4K3 b - - 0 1")).unwrap();
+    assert_ne!(with.hash, without.hash);
+}
@@ -0,0 +1,143 @@
+//! Structs for the limits of a search and for dividing the clock time over the moves.
+
+use crate::consts::{ Color };
+use crate::move_struct::{ Move };
+use std::time::{ Duration, Instant };
+use std::sync::atomic::{ AtomicBool, AtomicU64, Ordering };
+
+
+/// Default time that is kept aside for the communication with the GUI.
+pub const MOVE_OVERHEAD: u64 = 50;
+/// Moves to divide the remaining time over, when the GUI doesn't send ``movestogo``.
+const DEFAULT_MOVES_TO_GO: u64 = 30;
+
+
+/// Limits of a search, as sent with the [UCI](https://nl.wikipedia.org/wiki/Universal_Chess_Interface) ``go`` command.
+/// Times are in milliseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchLimits {
+    pub wtime: Option<u64>,
+    pub btime: Option<u64>,
+    pub winc: Option<u64>,
+    pub binc: Option<u64>,
+    pub movestogo: Option<u64>,
+    pub movetime: Option<u64>,
+    pub depth: Option<u8>,
+    pub nodes: Option<u64>,
+    pub infinite: bool,
+    /// Search on the opponent's time, the limits only count after the ``ponderhit``.
+    pub ponder: bool,
+    /// Only search these moves, all moves if empty.
+    pub searchmoves: Vec<Move>,
+}
+
+impl SearchLimits {
+    pub fn depth(depth: u8) -> SearchLimits {
+        //! Limits for a search to a fixed depth.
+
+        SearchLimits { depth: Some(depth), ..Default::default() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        //! True if there is nothing that limits the search.
+
+        let clock = [self.wtime, self.btime, self.movetime, self.nodes];
+        clock.iter().all(|limit| limit.is_none()) && self.depth.is_none() && !self.infinite
+    }
+}
+
+
+/// Keeps track of the time of a search.
+/// 
+/// The soft limit is the time after which no new iteration is started,
+/// the hard limit is the time after which the search is stopped immediately.
+/// While pondering the limits don't apply, after the ``ponderhit`` they count from the moment of the ponderhit.
+#[derive(Debug)]
+pub struct TimeManager {
+    start: Instant,
+    soft_limit: Option<Duration>,
+    hard_limit: Option<Duration>,
+    pondering: AtomicBool,
+    /// Milliseconds after the start from which the limits count.
+    limits_start: AtomicU64,
+}
+
+impl TimeManager {
+    pub fn new(limits: &SearchLimits, color: Color, move_overhead: u64) -> TimeManager {
+        //! Divide the time for the color that is on turn, keeping the move overhead (in milliseconds) aside.
+
+        let (time_left, increment) = match color {
+            Color::White => (limits.wtime, limits.winc.unwrap_or(0)),
+            Color::Black => (limits.btime, limits.binc.unwrap_or(0)),
+        };
+
+        let (soft_limit, hard_limit) = if limits.infinite {
+            (None, None)
+        } else if let Some(movetime) = limits.movetime {
+            let movetime = movetime.saturating_sub(move_overhead).max(1);
+            (Some(movetime), Some(movetime))
+        } else if let Some(time_left) = time_left {
+            let max_time = time_left.saturating_sub(move_overhead).max(1);
+            let moves_to_go = limits.movestogo.unwrap_or(DEFAULT_MOVES_TO_GO).clamp(1, 50);
+            let target = (time_left / moves_to_go + increment * 3 / 4).min(max_time);
+
+            // an iteration takes several times longer than the one before,
+            // so don't start one that can't be finished within the target time
+            (Some((target / 2).max(1)), Some((target * 2).min(max_time)))
+        } else {
+            (None, None)
+        };
+
+        TimeManager {
+            start: Instant::now(),
+            soft_limit: soft_limit.map(Duration::from_millis),
+            hard_limit: hard_limit.map(Duration::from_millis),
+            pondering: AtomicBool::new(limits.ponder),
+            limits_start: AtomicU64::new(0),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        //! Time since the start of the search.
+
+        self.start.elapsed()
+    }
+
+    #[allow(dead_code)]
+    pub fn soft_limit(&self) -> Option<Duration> {
+        self.soft_limit
+    }
+
+    pub fn hard_limit(&self) -> Option<Duration> {
+        self.hard_limit
+    }
+
+    pub fn pondering(&self) -> bool {
+        self.pondering.load(Ordering::Relaxed)
+    }
+
+    pub fn ponderhit(&self) {
+        //! The opponent played the expected move: from now on the search is a normal search.
+
+        self.limits_start.store(self.elapsed().as_millis() as u64, Ordering::Relaxed);
+        self.pondering.store(false, Ordering::Relaxed);
+    }
+
+    fn limits_elapsed(&self) -> Duration {
+        //! Time that counts for the limits.
+
+        self.elapsed().saturating_sub(Duration::from_millis(self.limits_start.load(Ordering::Relaxed)))
+    }
+
+    pub fn can_start_iteration(&self) -> bool {
+        //! True if there is time left for searching one ply deeper.
+
+        self.pondering() || self.soft_limit.is_none_or(|limit| self.limits_elapsed() < limit)
+    }
+
+    pub fn out_of_time(&self) -> bool {
+        //! True if the search has to stop right now.
+
+        !self.pondering() && self.hard_limit.is_some_and(|limit| self.limits_elapsed() >= limit)
+    }
+}
@@ -0,0 +1,202 @@
+//! Struct for the [transposition table](https://www.chessprogramming.org/Transposition_Table)
+//! 
+//! A fixed size hash table with the results of positions that were searched before.
+//! It is shared by the search threads without locking: the key is stored XOR-ed with the data,
+//! so an entry that is torn by two threads writing at the same time simply doesn't match anymore.
+
+use crate::consts::{ Bound, CHECK_MATE_SCORE, MATE_BOUND };
+use crate::move_struct::{ Move };
+use std::sync::atomic::{ AtomicU64, AtomicU8, Ordering };
+use std::mem;
+
+
+#[derive(Clone, Copy, Debug)]
+pub struct TTEntry {
+    pub depth: u8,
+    pub bound: Bound,
+    pub score: i64,
+    pub best_move: Option<Move>,
+}
+
+#[derive(Default)]
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+/// Two slots per bucket: the first one keeps the deepest search, the second one is always replaced.
+type Bucket = [Slot; 2];
+
+pub struct TranspositionTable {
+    buckets: Vec<Bucket>,
+    generation: AtomicU8,
+}
+
+
+impl TranspositionTable {
+    pub fn new(size_mb: usize) -> TranspositionTable {
+        //! Create a new (empty) transposition table that uses about the specified number of MB.
+
+        let n_buckets = (size_mb * 1024 * 1024 / mem::size_of::<Bucket>()).max(1);
+        let mut buckets = Vec::with_capacity(n_buckets);
+        buckets.resize_with(n_buckets, Bucket::default);
+
+        TranspositionTable {
+            buckets,
+            generation: AtomicU8::new(0),
+        }
+    }
+
+    pub fn clear(&self) {
+        //! Remove all entries.
+
+        for bucket in self.buckets.iter() {
+            for slot in bucket.iter() {
+                slot.key.store(0, Ordering::Relaxed);
+                slot.data.store(0, Ordering::Relaxed);
+            }
+        }
+        self.generation.store(0, Ordering::Relaxed);
+    }
+
+    pub fn new_search(&self) {
+        //! Age the table, entries of earlier searches will be replaced first.
+
+        let generation = (self.generation.load(Ordering::Relaxed) + 1) & 0x3f;
+        self.generation.store(generation, Ordering::Relaxed);
+    }
+
+    pub fn probe(&self, hash: u64, ply: usize) -> Option<TTEntry> {
+        //! Look up the position with the specified hash.
+        //! 
+        //! Mate scores are converted back from "mate from this position" to "mate from the root" using the ply.
+
+        for slot in self.bucket(hash).iter() {
+            let data = slot.data.load(Ordering::Relaxed);
+            if data != 0 && slot.key.load(Ordering::Relaxed) ^ data == hash {
+                let entry = unpack(data);
+                return Some(TTEntry { score: score_from_tt(entry.score, ply), ..entry });
+            }
+        }
+
+        None
+    }
+
+    pub fn store(&self, hash: u64, depth: u8, bound: Bound, score: i64, ply: usize, best_move: Option<Move>) {
+        //! Store the result of a search of the position with the specified hash.
+
+        let generation = self.generation.load(Ordering::Relaxed);
+        let bucket = self.bucket(hash);
+
+        // keep the best move of an earlier search if this search didn't find one
+        let mut best_move = best_move;
+        if best_move.is_none() {
+            best_move = self.probe(hash, ply).and_then(|entry| entry.best_move);
+        }
+
+        let deepest = &bucket[0];
+        let deepest_data = deepest.data.load(Ordering::Relaxed);
+        let replace_deepest = deepest_data == 0
+            || deepest.key.load(Ordering::Relaxed) ^ deepest_data == hash
+            || generation_of(deepest_data) != generation
+            || depth >= unpack(deepest_data).depth;
+        let slot = if replace_deepest { deepest } else { &bucket[1] };
+
+        let data = pack(TTEntry { depth, bound, score: score_to_tt(score, ply), best_move }, generation);
+        slot.key.store(hash ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
+    }
+
+    pub fn hashfull(&self) -> usize {
+        //! Per mille of the table that is used by the current search, estimated from the first buckets.
+
+        let generation = self.generation.load(Ordering::Relaxed);
+        let sample = &self.buckets[..self.buckets.len().min(500)];
+        let used = sample.iter().flatten().filter(|slot| {
+            let data = slot.data.load(Ordering::Relaxed);
+            data != 0 && generation_of(data) == generation
+        }).count();
+
+        used * 1000 / (sample.len() * 2)
+    }
+
+    fn bucket(&self, hash: u64) -> &Bucket {
+        //! Returns the bucket the hash belongs in.
+
+        &self.buckets[((hash as u128 * self.buckets.len() as u128) >> 64) as usize]
+    }
+}
+
+
+// data layout: 16 bits move, 32 bits score, 8 bits depth, 2 bits bound, 6 bits generation
+
+fn pack(entry: TTEntry, generation: u8) -> u64 {
+    let mve = entry.best_move.map_or(0, |mve| mve.to_u16()) as u64;
+    let score = score_to_i32(entry.score) as u32 as u64;
+
+    mve | score << 16 | (entry.depth as u64) << 48 | (entry.bound as u64) << 56 | (generation as u64) << 58
+}
+
+fn unpack(data: u64) -> TTEntry {
+    let mve = (data & 0xffff) as u16;
+
+    TTEntry {
+        depth: (data >> 48) as u8,
+        bound: match (data >> 56) & 0x3 {
+            1 => Bound::Exact,
+            2 => Bound::Lower,
+            _ => Bound::Upper,
+        },
+        score: score_from_i32((data >> 16) as u32 as i32),
+        best_move: if mve == 0 { None } else { Some(Move::from_u16(mve)) },
+    }
+}
+
+fn generation_of(data: u64) -> u8 {
+    (data >> 58) as u8
+}
+
+fn score_to_tt(score: i64, ply: usize) -> i64 {
+    //! Mate scores are relative to the root in the search, but relative to the position in the table.
+
+    if score >= MATE_BOUND {
+        score.saturating_add(ply as i64)
+    } else if score <= -MATE_BOUND {
+        score.saturating_sub(ply as i64).max(-CHECK_MATE_SCORE)
+    } else {
+        score
+    }
+}
+
+fn score_from_tt(score: i64, ply: usize) -> i64 {
+    if score >= MATE_BOUND {
+        score.saturating_sub(ply as i64)
+    } else if score <= -MATE_BOUND {
+        score.saturating_add(ply as i64)
+    } else {
+        score
+    }
+}
+
+fn score_to_i32(score: i64) -> i32 {
+    //! Mate scores are stored as the distance to ``i32::MAX``, other scores as they are.
+
+    if score >= MATE_BOUND {
+        i32::MAX - (CHECK_MATE_SCORE - score) as i32
+    } else if score <= -MATE_BOUND {
+        -i32::MAX + (CHECK_MATE_SCORE + score) as i32
+    } else {
+        score.clamp(i32::MIN as i64 / 2, i32::MAX as i64 / 2) as i32
+    }
+}
+
+fn score_from_i32(score: i32) -> i64 {
+    let distance = CHECK_MATE_SCORE - MATE_BOUND;
+    if score as i64 >= i32::MAX as i64 - distance {
+        CHECK_MATE_SCORE - (i32::MAX - score) as i64
+    } else if score as i64 <= -(i32::MAX as i64) + distance {
+        -CHECK_MATE_SCORE + (score + i32::MAX) as i64
+    } else {
+        score as i64
+    }
+}
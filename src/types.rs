@@ -1,2 +1,3 @@
 
 pub type Cord = [usize; 2];
+pub type Bitboard = u64;
@@ -4,10 +4,181 @@ use std::sync::mpsc::{ Receiver, self };
 use std::sync::mpsc::TryRecvError;
 use crate::logger::{ Logger, LogType };
 use crate::game::{ Game };
-use crate::move_struct::{ Move };
-use crate::consts::{ SEARCH_DEPTH };
-use crate::openings::{ OpeningsDatabase };
+use crate::consts::{ SEARCH_DEPTH, DEFAULT_HASH_SIZE };
+use crate::transposition_table::{ TranspositionTable };
+use crate::uci_command::{ UciCommand, GoCommand, parse_command, START_FEN };
+use crate::search::{ SearchContext, SearchOptions, RootMove, MAX_SKILL_LEVEL, default_threads };
+use crate::time_manager::{ MOVE_OVERHEAD };
+use std::sync::{ Arc };
+use crate::openings::{ OpeningsDatabase, DEFAULT_BOOK_FILE };
 use std::time::{ Duration };
+use std::fs::{ File };
+use crate::pgn::{ PgnGame };
+use chrono::{ Utc };
+
+
+const DEFAULT_LOG_FILE: &str = "log.log";
+/// Range of the strength that can be set with ``UCI_Elo``.
+const MIN_ELO: i64 = 800;
+const MAX_ELO: i64 = 2400;
+
+
+/// Value of an engine option, the type follows from the default value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OptionValue {
+    Check(bool),
+    Spin(i64),
+    String(String),
+}
+
+/// An option that the GUI can change with ``setoption``.
+pub struct UciOption {
+    pub name: &'static str,
+    default: OptionValue,
+    /// Bounds of a spin option.
+    min: i64,
+    max: i64,
+    value: OptionValue,
+}
+
+impl UciOption {
+    fn check(name: &'static str, default: bool) -> UciOption {
+        UciOption { name, default: OptionValue::Check(default), min: 0, max: 0, value: OptionValue::Check(default) }
+    }
+
+    fn spin(name: &'static str, default: i64, min: i64, max: i64) -> UciOption {
+        UciOption { name, default: OptionValue::Spin(default), min, max, value: OptionValue::Spin(default) }
+    }
+
+    fn string(name: &'static str, default: &str) -> UciOption {
+        UciOption { name, default: OptionValue::String(String::from(default)), min: 0, max: 0, value: OptionValue::String(String::from(default)) }
+    }
+
+    pub fn declaration(&self) -> String {
+        //! The line that tells the GUI about this option.
+
+        match &self.default {
+            OptionValue::Check(default) => format!("option name {} type check default {}", self.name, default),
+            OptionValue::Spin(default) => format!("option name {} type spin default {} min {} max {}", self.name, default, self.min, self.max),
+            OptionValue::String(default) if default.is_empty() => format!("option name {} type string default <empty>", self.name),
+            OptionValue::String(default) => format!("option name {} type string default {}", self.name, default),
+        }
+    }
+
+    pub fn parse(&self, value: &str) -> Result<OptionValue, String> {
+        //! Read a value sent by the GUI, checking that it fits this option.
+
+        match self.default {
+            OptionValue::Check(_) => match value {
+                "true" => Ok(OptionValue::Check(true)),
+                "false" => Ok(OptionValue::Check(false)),
+                _ => Err(format!("`{}` is not true or false", value)),
+            },
+            OptionValue::Spin(_) => match value.parse::<i64>() {
+                Ok(number) if (self.min..=self.max).contains(&number) => Ok(OptionValue::Spin(number)),
+                _ => Err(format!("`{}` is not a number from {} to {}", value, self.min, self.max)),
+            },
+            OptionValue::String(_) if value == "<empty>" => Ok(OptionValue::String(String::new())),
+            OptionValue::String(_) => Ok(OptionValue::String(String::from(value))),
+        }
+    }
+}
+
+/// All the options of the engine with their current values.
+pub struct EngineOptions {
+    pub options: Vec<UciOption>,
+}
+
+impl EngineOptions {
+    pub fn new() -> EngineOptions {
+        EngineOptions {
+            options: vec![
+                UciOption::spin("Hash", DEFAULT_HASH_SIZE as i64, 1, 4096),
+                UciOption::spin("Threads", default_threads() as i64, 1, 256),
+                UciOption::spin("MultiPV", 1, 1, 256),
+                UciOption::check("Ponder", false),
+                UciOption::check("OwnBook", true),
+                UciOption::string("BookFile", DEFAULT_BOOK_FILE),
+                UciOption::spin("Contempt", 0, -100, 100),
+                UciOption::spin("Move Overhead", MOVE_OVERHEAD as i64, 0, 5000),
+                UciOption::string("SyzygyPath", ""),
+                UciOption::spin("Skill Level", MAX_SKILL_LEVEL as i64, 0, MAX_SKILL_LEVEL as i64),
+                UciOption::spin("UCI_Elo", MAX_ELO, MIN_ELO, MAX_ELO),
+                UciOption::check("UCI_LimitStrength", false),
+                UciOption::check("UCI_Chess960", false),
+                UciOption::check("Null Move Pruning", true),
+                UciOption::check("Late Move Reductions", true),
+                UciOption::check("Futility Pruning", true),
+                UciOption::check("Check Extensions", true),
+                UciOption::string("Debug Log File", DEFAULT_LOG_FILE),
+            ],
+        }
+    }
+
+    pub fn set(&mut self, name: &str, value: Option<&str>) -> Result<&'static str, String> {
+        //! Change the value of an option, option names are case insensitive.
+        //! 
+        //! Returns the name of the changed option.
+
+        let option = self.options.iter_mut().find(|option| option.name.eq_ignore_ascii_case(name)).ok_or(format!("Unknown option `{}`", name))?;
+        let value = option.parse(value.unwrap_or(""))?;
+        if option.name == "UCI_Chess960" && value == OptionValue::Check(true) {
+            return Err(String::from("Chess960 is not supported"));
+        }
+
+        option.value = value;
+        Ok(option.name)
+    }
+
+    pub fn value(&self, name: &str) -> &OptionValue {
+        &self.options.iter().find(|option| option.name == name).unwrap().value
+    }
+
+    pub fn check(&self, name: &str) -> bool {
+        match self.value(name) {
+            OptionValue::Check(value) => *value,
+            value => panic!("Option `{}` is not a check but {:?}", name, value),
+        }
+    }
+
+    pub fn spin(&self, name: &str) -> i64 {
+        match self.value(name) {
+            OptionValue::Spin(value) => *value,
+            value => panic!("Option `{}` is not a spin but {:?}", name, value),
+        }
+    }
+
+    pub fn string(&self, name: &str) -> &str {
+        match self.value(name) {
+            OptionValue::String(value) => value,
+            value => panic!("Option `{}` is not a string but {:?}", name, value),
+        }
+    }
+
+    pub fn search_options(&self) -> SearchOptions {
+        //! The options for the next search.
+
+        // a limited strength is translated to a skill level
+        let skill_level = if self.check("UCI_LimitStrength") {
+            (self.spin("UCI_Elo") - MIN_ELO) * MAX_SKILL_LEVEL as i64 / (MAX_ELO - MIN_ELO)
+        } else {
+            self.spin("Skill Level")
+        };
+
+        SearchOptions {
+            threads: self.spin("Threads") as usize,
+            multi_pv: self.spin("MultiPV") as usize,
+            own_book: self.check("OwnBook"),
+            contempt: self.spin("Contempt"),
+            move_overhead: self.spin("Move Overhead") as u64,
+            skill_level: skill_level as u8,
+            null_move: self.check("Null Move Pruning"),
+            late_move_reductions: self.check("Late Move Reductions"),
+            futility_pruning: self.check("Futility Pruning"),
+            check_extensions: self.check("Check Extensions"),
+        }
+    }
+}
 
 
 fn spawn_stdin_channel() -> Receiver<String> {
@@ -18,10 +189,14 @@ fn spawn_stdin_channel() -> Receiver<String> {
     let (tx, rx) = mpsc::channel::<String>();
     thread::spawn(move || loop {
         let mut buffer = String::new();
-        io::stdin().read_line(&mut buffer).unwrap();
+        if io::stdin().read_line(&mut buffer).unwrap() == 0 {
+            // stdin is closed, so the engine can't get any more commands
+            let _ = tx.send(String::from("quit"));
+            break;
+        }
         buffer = buffer.trim().to_string();
-        if !buffer.is_empty() {
-            tx.send(buffer).unwrap();
+        if !buffer.is_empty() && tx.send(buffer).is_err() {
+            break;
         }
     });
     rx
@@ -31,15 +206,17 @@ fn spawn_stdin_channel() -> Receiver<String> {
 pub fn uci() {
     //! Main function to spawn stdin channel and handle the [UCI](https://nl.wikipedia.org/wiki/Universal_Chess_Interface)-input
 
-    let logger = Logger::new("log.log");
+    let mut options = EngineOptions::new();
+    let mut logger = Logger::new(DEFAULT_LOG_FILE);
 
     let mut debug_mode = false;
-    let openings_database = OpeningsDatabase::new();
+    let mut openings_database = OpeningsDatabase::new();
+    let mut transposition_table = Arc::new(TranspositionTable::new(DEFAULT_HASH_SIZE));
 
-    let mut search_thread: Option<thread::JoinHandle<()>> = None;
-    #[allow(unused_assignments)]
-    let (mut search_thread_sender, mut search_thread_receiver) = mpsc::channel::<Move>();
-    let mut game: Game = Game::from_fen(String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"));
+    let mut search_thread: Option<(thread::JoinHandle<Vec<RootMove>>, Arc<SearchContext>)> = None;
+    let mut game: Game = Game::from_fen(String::from(START_FEN)).unwrap();
+    // the position the moves of the game start from
+    let mut start_position = game.clone();
     let mut got_initial_position = false;
     let stdin_channel = spawn_stdin_channel();
 
@@ -47,109 +224,235 @@ pub fn uci() {
         match stdin_channel.try_recv() {
             Ok(message) => {
                 logger.log(LogType::Info, format!("Received: {}", message));
-                let splitted_buffer = message.split(' ').collect::<Vec<&str>>();
-                let command = splitted_buffer[0];
-
-                if command == "uci" {
-                    println!("id name Beth {}", env!("CARGO_PKG_VERSION"));
-                    println!("id author {}", env!("CARGO_PKG_AUTHORS"));
-                    println!("uciok");
-                } else if command == "debug" {
-                    debug_mode = !debug_mode;
-                } else if command == "isready" {
-                    println!("readyok");
-                } else if command == "quit" {
-                    break 'main;
-                } else if command == "position" {
-                    let mut start_fen_string = splitted_buffer[1].to_string();
-                    if start_fen_string == "startpos" {
-                        start_fen_string = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string();
-                    }
-
-                    let mut looking_at_move = false;
-                    let mut moves = Vec::new();
-                    game = Game::from_fen(start_fen_string);
-                    for item in splitted_buffer.iter() {
-                        if looking_at_move {
-                            let m = Move::from_long_algebraic_notation(String::from(*item));
-                            moves.push(m);
-                        } else if *item == "moves" {
-                            looking_at_move = true
-                        }
-                    }
-
-                    game.apply_moves(&moves);
-                    got_initial_position = true;
-
-                    let mut board_text = String::from("Board:\n");
-                    for y in 0..8 {
-                        for x in 0..8 {
-                            if game.board[7 - y][x].is_some() {
-                                board_text += &game.board[7 - y][x].unwrap().to_fen();
-                            } else {
-                                board_text += " ";
-                            }
+                let command = match parse_command(&message) {
+                    Ok(command) => command,
+                    Err(error) => {
+                        logger.log(LogType::Warn, format!("Couldn't parse `{}`: {}", message, error));
+                        if debug_mode {
+                            println!("info string {}", error);
                         }
-                        board_text += "\n";
-                    }
-                    logger.log(LogType::Info, board_text);
-                } else if command == "go" && got_initial_position {
-                    // game.show_board(None, Color::Black);
-                    // println!("FENCODE: {}", game.to_fen());
-
-                    let game_clone = game.clone();
-                    let thread_communicators = mpsc::channel::<Move>();
-                    search_thread_sender = thread_communicators.0;
-                    search_thread_receiver = thread_communicators.1;
-
-                    let odb = openings_database.clone();
-                    search_thread = Some(thread::spawn(move || {
-                        let best_move = game_clone.get_best_move(SEARCH_DEPTH, &odb);
-                        search_thread_sender.send(best_move).unwrap();
-                    }));
-                } else if command == "stop" {
-                    if search_thread.is_some() {
-
-                    }
-                } else {
-                    logger.log(LogType::Warn, format!("Couldn't handle command `{}` at this time", command));
+                        continue;
+                    },
+                };
+
+                // a new search or position doesn't leave the old search running in the background
+                if matches!(command, UciCommand::Go(_) | UciCommand::Position { .. } | UciCommand::UciNewGame) {
+                    stop_search(&mut search_thread, &mut game, &logger);
                 }
-            },
-            Err(TryRecvError::Empty) => {
-                match &search_thread {
-                    Some(_) => {
-                        match search_thread_receiver.try_recv() {
-                            Ok(mve) => {
-                                game.do_move(&mve);
-
-                                let mut board_text = String::from("Board:\n");
-                                for y in 0..8 {
-                                    for x in 0..8 {
-                                        if game.board[7 - y][x].is_some() {
-                                            board_text += &game.board[7 - y][x].unwrap().to_fen();
-                                        } else {
-                                            board_text += " ";
-                                        }
-                                    }
-                                    board_text += "\n";
+
+                match command {
+                    UciCommand::Uci => {
+                        println!("id name Beth {}", env!("CARGO_PKG_VERSION"));
+                        println!("id author {}", env!("CARGO_PKG_AUTHORS"));
+                        for option in options.options.iter() {
+                            println!("{}", option.declaration());
+                        }
+                        println!("uciok");
+                    },
+                    UciCommand::Debug(on) => debug_mode = on,
+                    UciCommand::IsReady => println!("readyok"),
+                    UciCommand::SetOption { name, value } => {
+                        match options.set(&name, value.as_deref()) {
+                            // most options are read at the start of a search, these change something right away
+                            Ok("Hash") => transposition_table = Arc::new(TranspositionTable::new(options.spin("Hash") as usize)),
+                            Ok("BookFile") => match OpeningsDatabase::from_file(options.string("BookFile")) {
+                                Ok(database) => openings_database = database,
+                                Err(error) => logger.log(LogType::Warn, error),
+                            },
+                            Ok("Debug Log File") => {
+                                let file_name = options.string("Debug Log File");
+                                match File::options().create(true).append(true).open(file_name) {
+                                    Ok(_) => logger = Logger::new(file_name),
+                                    Err(error) => logger.log(LogType::Warn, format!("Can't log to `{}`: {}", file_name, error)),
                                 }
-                                logger.log(LogType::Info, board_text);
+                            },
+                            Ok("SyzygyPath") if !options.string("SyzygyPath").is_empty() => {
+                                logger.log(LogType::Warn, String::from("Syzygy tablebases are not supported, SyzygyPath is ignored"));
+                                println!("info string Syzygy tablebases are not supported");
+                            },
+                            Ok(_) => {},
+                            Err(error) => {
+                                logger.log(LogType::Warn, format!("Couldn't set option `{}`: {}", name, error));
+                                if debug_mode {
+                                    println!("info string {}", error);
+                                }
+                            },
+                        }
+                    },
+                    UciCommand::Register { .. } => {
+                        // there is nothing to register
+                    },
+                    UciCommand::UciNewGame => {
+                        log_game(&start_position, &game, &logger);
+                        game = Game::from_fen(String::from(START_FEN)).unwrap();
+                        start_position = game.clone();
+                        got_initial_position = false;
+                        transposition_table.clear();
+                    },
+                    UciCommand::Position { fen, moves } => {
+                        game = match Game::from_fen(fen.clone()) {
+                            Ok(new_game) => new_game,
+                            Err(error) => {
+                                logger.log(LogType::Warn, format!("Invalid FEN `{}`: {}", fen, error));
+                                if debug_mode {
+                                    println!("info string {}", error);
+                                }
+                                got_initial_position = false;
+                                continue;
+                            },
+                        };
+                        start_position = game.clone();
+                        for item in moves.iter() {
+                            match game.find_legal_move(item) {
+                                Some(m) => game.do_move(&m),
+                                None => {
+                                    logger.log(LogType::Warn, format!("Illegal move `{}` in position command, ignoring the rest of the moves", item));
+                                    break;
+                                },
+                            }
+                        }
 
-                                logger.log(LogType::Info, mve.repr());
+                        got_initial_position = true;
+                        log_board(&game, &logger);
+                    },
+                    UciCommand::Go(_) if !got_initial_position => {
+                        logger.log(LogType::Warn, String::from("Received go before a position"));
+                    },
+                    UciCommand::Go(GoCommand { perft: Some(depth), .. }) => {
+                        // non standard extension: count the leaf nodes per root move
+                        let mut total = 0;
+                        for (mve, nodes) in game.perft_divide(depth).iter() {
+                            println!("{}: {}", mve.long_algebraic_notation(), nodes);
+                            total += nodes;
+                        }
+                        println!("\nNodes searched: {}", total);
+                    },
+                    UciCommand::Go(go) => {
+                        // without any limits search to the default depth
+                        let mut limits = go.limits;
+                        if limits.is_empty() {
+                            limits.depth = Some(SEARCH_DEPTH);
+                        }
+                        for notation in go.searchmoves.iter() {
+                            match game.find_legal_move(notation) {
+                                Some(mve) => limits.searchmoves.push(mve),
+                                None => logger.log(LogType::Warn, format!("Illegal move `{}` in searchmoves", notation)),
+                            }
+                        }
 
-                                println!("bestmove {}", mve.long_algebraic_notation());
-                                search_thread = None;
-                            },
-                            Err(TryRecvError::Empty) => {},
-                            Err(TryRecvError::Disconnected) => panic!("Search thread connection lost"),
+                        let game_clone = game.clone();
+                        let odb = openings_database.clone();
+                        let mut search = SearchContext::new(limits, options.search_options(), game.on_turn, &transposition_table);
+                        search.uci_output = true;
+                        let search = Arc::new(search);
+                        let thread_search = Arc::clone(&search);
+                        search_thread = Some((thread::spawn(move || game_clone.get_best_moves(&thread_search, &odb)), search));
+                    },
+                    UciCommand::Stop => {
+                        stop_search(&mut search_thread, &mut game, &logger);
+                    },
+                    UciCommand::PonderHit => {
+                        // keep searching, but from now on the time counts
+                        match &search_thread {
+                            Some((_, search)) => search.time_manager.ponderhit(),
+                            None => logger.log(LogType::Warn, String::from("Received ponderhit without a search")),
                         }
                     },
-                    None => {},
+                    UciCommand::Quit => {
+                        // stop the search and wait for its threads, the move isn't needed anymore
+                        if let Some((handle, search)) = search_thread.take() {
+                            search.stop();
+                            handle.join().unwrap();
+                        }
+                        log_game(&start_position, &game, &logger);
+                        break 'main;
+                    },
+                }
+            },
+            Err(TryRecvError::Empty) => {
+                // an infinite search only sends its move after the stop command, a ponder search after the ponderhit
+                let finished = search_thread.as_ref().is_some_and(|(handle, search)| {
+                    handle.is_finished() && !search.limits.infinite && !search.time_manager.pondering()
+                });
+                if finished {
+                    let (handle, search) = search_thread.take().unwrap();
+                    finish_search(&mut game, handle.join().unwrap().first(), &search, &logger);
                 }
             },
             Err(TryRecvError::Disconnected) => panic!("Channel disconnected"),
         }
 
-        thread::sleep(Duration::from_millis(100));
+        // short enough to not lose much time between the end of the search and sending the move
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+
+fn log_board(game: &Game, logger: &Logger) {
+    //! Write the board to the log file.
+
+    let mut board_text = String::from("Board:\n");
+    for y in 0..8 {
+        for x in 0..8 {
+            if game.board[7 - y][x].is_some() {
+                board_text += &game.board[7 - y][x].unwrap().to_fen();
+            } else {
+                board_text += " ";
+            }
+        }
+        board_text += "\n";
+    }
+    logger.log(LogType::Info, board_text);
+}
+
+
+fn log_game(start_position: &Game, game: &Game, logger: &Logger) {
+    //! Write the moves played from the start position to the log file in PGN, so the games of the engine can be archived.
+
+    if game.moves.is_empty() {
+        return;
+    }
+
+    let mut pgn_game = PgnGame::new(start_position, &game.moves);
+    pgn_game.set_tag("Event", &format!("Beth {} UCI game", env!("CARGO_PKG_VERSION")));
+    pgn_game.set_tag("Date", &Utc::now().format("%Y.%m.%d").to_string());
+    match pgn_game.to_pgn() {
+        Ok(pgn) => logger.log(LogType::Info, format!("Game:\n{}", pgn)),
+        Err(error) => logger.log(LogType::Warn, format!("Can't write the game to PGN: {}", error)),
+    }
+}
+
+
+fn stop_search(search_thread: &mut Option<(thread::JoinHandle<Vec<RootMove>>, Arc<SearchContext>)>, game: &mut Game, logger: &Logger) {
+    //! Stop the running search (if any), wait for its threads and send its move like a search that finished by itself.
+    //! 
+    //! The search returns the best move of the last finished iteration.
+
+    if let Some((handle, search)) = search_thread.take() {
+        search.stop();
+        finish_search(game, handle.join().unwrap().first(), &search, logger);
+    }
+}
+
+
+fn finish_search(game: &mut Game, root_move: Option<&RootMove>, search: &SearchContext, logger: &Logger) {
+    //! Apply the move the search found to the game and send it to the GUI, together with the move to ponder on.
+    //! 
+    //! When the game is over there is no move, which is sent as the null move ``0000``.
+
+    let Some(root_move) = root_move else {
+        logger.log(LogType::Info, String::from("No legal moves"));
+        println!("bestmove 0000");
+        return;
+    };
+    let mve = root_move.mve;
+    let ponder_move = game.ponder_move(root_move, &search.transposition_table);
+    logger.log(LogType::Info, format!("Playing {}", mve.to_san(game)));
+    game.do_move(&mve);
+    log_board(game, logger);
+
+    match ponder_move {
+        Some(ponder_move) => println!("bestmove {} ponder {}", mve.long_algebraic_notation(), ponder_move.long_algebraic_notation()),
+        None => println!("bestmove {}", mve.long_algebraic_notation()),
     }
 }
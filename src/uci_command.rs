@@ -0,0 +1,200 @@
+//! Parser for the commands a GUI sends with the [UCI](https://nl.wikipedia.org/wiki/Universal_Chess_Interface) protocol.
+//!
+//! Every line is turned into a typed ``UciCommand``, a malformed line gives an ``UciError`` instead of a panic.
+
+use crate::time_manager::{ SearchLimits };
+use crate::move_struct::{ Move };
+use std::fmt;
+
+
+/// The FEN of the normal starting position.
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UciCommand {
+    Uci,
+    Debug(bool),
+    IsReady,
+    SetOption { name: String, value: Option<String> },
+    /// ``register later`` has neither a name nor a code.
+    Register { name: Option<String>, code: Option<String> },
+    UciNewGame,
+    Position { fen: String, moves: Vec<String> },
+    Go(GoCommand),
+    Stop,
+    PonderHit,
+    Quit,
+}
+
+/// The parameters of a ``go`` command.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GoCommand {
+    pub limits: SearchLimits,
+    /// Only search these moves (in long algebraic notation).
+    pub searchmoves: Vec<String>,
+    /// Non standard extension: ``go perft <depth>``.
+    pub perft: Option<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UciError {
+    Empty,
+    UnknownCommand(String),
+    MissingArgument { command: &'static str, argument: &'static str },
+    InvalidValue { argument: String, value: String },
+}
+
+impl fmt::Display for UciError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UciError::Empty => write!(formatter, "empty command"),
+            UciError::UnknownCommand(command) => write!(formatter, "unknown command `{}`", command),
+            UciError::MissingArgument { command, argument } => write!(formatter, "`{}` is missing `{}`", command, argument),
+            UciError::InvalidValue { argument, value } => write!(formatter, "invalid value `{}` for `{}`", value, argument),
+        }
+    }
+}
+
+
+pub fn parse_command(line: &str) -> Result<UciCommand, UciError> {
+    //! Parse a single line of input.
+    //!
+    //! As the protocol prescribes, unknown tokens in front of the command are skipped (``joho debug on`` is ``debug on``).
+
+    let tokens = line.split_whitespace().collect::<Vec<&str>>();
+    let start = tokens.iter().position(|token| is_command(token)).ok_or_else(|| match tokens.first() {
+        Some(token) => UciError::UnknownCommand(token.to_string()),
+        None => UciError::Empty,
+    })?;
+    let arguments = &tokens[start + 1..];
+
+    match tokens[start] {
+        "uci" => Ok(UciCommand::Uci),
+        "debug" => match arguments.first() {
+            Some(&"on") => Ok(UciCommand::Debug(true)),
+            Some(&"off") => Ok(UciCommand::Debug(false)),
+            Some(value) => Err(UciError::InvalidValue { argument: String::from("debug"), value: value.to_string() }),
+            None => Err(UciError::MissingArgument { command: "debug", argument: "on|off" }),
+        },
+        "isready" => Ok(UciCommand::IsReady),
+        "setoption" => parse_setoption(arguments),
+        "register" => parse_register(arguments),
+        "ucinewgame" => Ok(UciCommand::UciNewGame),
+        "position" => parse_position(arguments),
+        "go" => parse_go(arguments),
+        "stop" => Ok(UciCommand::Stop),
+        "ponderhit" => Ok(UciCommand::PonderHit),
+        "quit" => Ok(UciCommand::Quit),
+        command => Err(UciError::UnknownCommand(command.to_string())),
+    }
+}
+
+fn is_command(token: &str) -> bool {
+    ["uci", "debug", "isready", "setoption", "register", "ucinewgame", "position", "go", "stop", "ponderhit", "quit"].contains(&token)
+}
+
+fn parse_setoption(arguments: &[&str]) -> Result<UciCommand, UciError> {
+    //! ``setoption name <id> [value <x>]``, the name and the value may contain spaces.
+
+    if arguments.first() != Some(&"name") {
+        return Err(UciError::MissingArgument { command: "setoption", argument: "name" });
+    }
+
+    let value_idx = arguments.iter().position(|token| *token == "value");
+    let name = arguments[1..value_idx.unwrap_or(arguments.len())].join(" ");
+    if name.is_empty() {
+        return Err(UciError::MissingArgument { command: "setoption", argument: "name" });
+    }
+    let value = value_idx.map(|idx| arguments[idx + 1..].join(" "));
+
+    Ok(UciCommand::SetOption { name, value })
+}
+
+fn parse_register(arguments: &[&str]) -> Result<UciCommand, UciError> {
+    //! ``register later`` or ``register name <x> code <y>``.
+
+    if arguments.first() == Some(&"later") {
+        return Ok(UciCommand::Register { name: None, code: None });
+    }
+
+    let name_idx = arguments.iter().position(|token| *token == "name");
+    let code_idx = arguments.iter().position(|token| *token == "code");
+    let name = name_idx.map(|idx| arguments[idx + 1..code_idx.filter(|code_idx| *code_idx > idx).unwrap_or(arguments.len())].join(" "));
+    let code = code_idx.map(|idx| arguments[idx + 1..name_idx.filter(|name_idx| *name_idx > idx).unwrap_or(arguments.len())].join(" "));
+    if name.is_none() && code.is_none() {
+        return Err(UciError::MissingArgument { command: "register", argument: "later|name|code" });
+    }
+
+    Ok(UciCommand::Register { name, code })
+}
+
+fn parse_position(arguments: &[&str]) -> Result<UciCommand, UciError> {
+    //! ``position [fen <fenstring> | startpos] moves <move1> .... <movei>``
+
+    let moves_idx = arguments.iter().position(|token| *token == "moves").unwrap_or(arguments.len());
+    let fen = match arguments.first() {
+        Some(&"startpos") => String::from(START_FEN),
+        Some(&"fen") => {
+            let fields = &arguments[1..moves_idx];
+            if !(4..=6).contains(&fields.len()) {
+                return Err(UciError::InvalidValue { argument: String::from("fen"), value: fields.join(" ") });
+            }
+
+            // the clocks are optional in practice
+            let mut fields = fields.to_vec();
+            fields.extend_from_slice(&["0", "1"][fields.len() - 4..]);
+            fields.join(" ")
+        },
+        Some(value) => return Err(UciError::InvalidValue { argument: String::from("position"), value: value.to_string() }),
+        None => return Err(UciError::MissingArgument { command: "position", argument: "startpos|fen" }),
+    };
+    let moves = arguments.iter().skip(moves_idx + 1).map(|mve| mve.to_string()).collect();
+
+    Ok(UciCommand::Position { fen, moves })
+}
+
+fn parse_go(arguments: &[&str]) -> Result<UciCommand, UciError> {
+    //! ``go`` with any of: ``searchmoves <moves>``, ``ponder``, ``wtime <x>``, ``btime <x>``, ``winc <x>``, ``binc <x>``,
+    //! ``movestogo <x>``, ``depth <x>``, ``nodes <x>``, ``movetime <x>``, ``infinite`` and ``perft <x>``.
+    //!
+    //! Unknown parameters are ignored.
+
+    let mut go = GoCommand::default();
+    let mut idx = 0;
+    while idx < arguments.len() {
+        let argument = arguments[idx];
+        match argument {
+            "infinite" => go.limits.infinite = true,
+            "ponder" => go.limits.ponder = true,
+            "searchmoves" => {
+                while let Some(mve) = arguments.get(idx + 1).filter(|token| Move::is_long_algebraic_notation(token)) {
+                    go.searchmoves.push(mve.to_string());
+                    idx += 1;
+                }
+            },
+            "wtime" | "btime" | "winc" | "binc" | "movestogo" | "movetime" | "nodes" | "depth" | "perft" => {
+                let value = arguments.get(idx + 1).ok_or(UciError::MissingArgument { command: "go", argument: "value" })?;
+                let number = value.parse::<i64>().map_err(|_| UciError::InvalidValue { argument: argument.to_string(), value: value.to_string() })?;
+                // some GUIs send a negative time when the clock ran out
+                let number = number.max(0) as u64;
+                match argument {
+                    "wtime" => go.limits.wtime = Some(number),
+                    "btime" => go.limits.btime = Some(number),
+                    "winc" => go.limits.winc = Some(number),
+                    "binc" => go.limits.binc = Some(number),
+                    "movestogo" => go.limits.movestogo = Some(number),
+                    "movetime" => go.limits.movetime = Some(number),
+                    "nodes" => go.limits.nodes = Some(number),
+                    "depth" => go.limits.depth = Some(number.min(u8::MAX as u64) as u8),
+                    _ => go.perft = Some(number.min(u8::MAX as u64) as u8),
+                }
+                idx += 1;
+            },
+            _ => {},
+        }
+        idx += 1;
+    }
+
+    Ok(UciCommand::Go(go))
+}
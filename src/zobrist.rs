@@ -0,0 +1,65 @@
+//! Random keys for [Zobrist hashing](https://www.chessprogramming.org/Zobrist_Hashing) of a position.
+//! 
+//! The hash of a position is the XOR of the keys of every piece on its square,
+//! the side to move, the castling abilities and the en passant file.
+
+use crate::consts::{ Color, PieceType };
+use crate::piece::{ Piece };
+
+
+// 12 * 64 piece keys, 1 side key, 4 castle keys and 8 en passant keys
+const N_KEYS: usize = 12 * 64 + 1 + 4 + 8;
+const SIDE_KEY_IDX: usize = 12 * 64;
+const CASTLE_KEYS_IDX: usize = SIDE_KEY_IDX + 1;
+const EN_PASSANT_KEYS_IDX: usize = CASTLE_KEYS_IDX + 4;
+
+const KEYS: [u64; N_KEYS] = generate_keys(0x9e37_79b9_7f4a_7c15);
+
+
+const fn generate_keys(mut seed: u64) -> [u64; N_KEYS] {
+    //! Generate the keys with [SplitMix64](https://prng.di.unimi.it/splitmix64.c), so they are the same every run.
+
+    let mut keys = [0; N_KEYS];
+    let mut idx = 0;
+    while idx < N_KEYS {
+        seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        keys[idx] = z ^ (z >> 31);
+        idx += 1;
+    }
+
+    keys
+}
+
+pub fn piece_key(piece: &Piece, square: usize) -> u64 {
+    //! Key for the piece standing on the square (index ``y * 8 + x``).
+
+    KEYS[((piece.color as usize) * 6 + piece.piece_type as usize) * 64 + square]
+}
+
+pub fn side_key() -> u64 {
+    //! Key that is in the hash when black is on turn.
+
+    KEYS[SIDE_KEY_IDX]
+}
+
+pub fn castle_key(castle: &[Piece]) -> u64 {
+    //! Key for the castling abilities. King = king's side, Queen = queen's side.
+
+    let mut key = 0;
+    for piece in castle.iter() {
+        let side = if piece.piece_type == PieceType::King { 0 } else { 1 };
+        let color = if piece.color == Color::White { 0 } else { 2 };
+        key ^= KEYS[CASTLE_KEYS_IDX + color + side];
+    }
+
+    key
+}
+
+pub fn en_passant_key(file: usize) -> u64 {
+    //! Key for an en passant capture being possible on the specified file.
+
+    KEYS[EN_PASSANT_KEYS_IDX + file]
+}